@@ -0,0 +1,398 @@
+//! Derive macro for the `Nullable` trait from the [`inline-option`](https://docs.rs/inline-option) crate.
+//!
+//! This crate is not meant to be used directly. Enable the `derive` feature of `inline-option` instead.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::TokenTree;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, Generics, Ident,
+    Member, Type, spanned::Spanned,
+};
+
+/// Derives `Nullable` for a struct or enum.
+///
+/// See the documentation of `inline_option::Nullable` for the supported attributes.
+#[proc_macro_derive(Nullable, attributes(nullable))]
+pub fn derive_nullable(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut generics = input.generics.clone();
+    let args = parse_args(&input.attrs)?;
+    let (null, is_null) = match args {
+        Args {
+            null: Some(null),
+            via: None,
+        } => {
+            reject_inner_attrs(&input.data)?;
+            let is_null = null_eq(&input.data, &mut generics);
+            (null, is_null)
+        }
+        Args {
+            null: None,
+            via: Some(via),
+        } => match &input.data {
+            Data::Struct(data) => struct_via(data, &via)?,
+            _ => {
+                return Err(syn::Error::new(
+                    via.span(),
+                    "`via` is only supported on structs",
+                ));
+            }
+        },
+        Args {
+            null: Some(_),
+            via: Some(via),
+        } => {
+            return Err(syn::Error::new(
+                via.span(),
+                "`null` and `via` cannot be used together",
+            ));
+        }
+        Args {
+            null: None,
+            via: None,
+        } => match &input.data {
+            Data::Enum(data) => enum_null(&input.ident, data)?,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "expected a `#[nullable(null = ...)]` or `#[nullable(via = ...)]` attribute",
+                ));
+            }
+        },
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::inline_option::Nullable for #name #ty_generics #where_clause {
+            const NULL: Self = #null;
+
+            #[inline]
+            fn is_null(&self) -> bool {
+                #is_null
+            }
+        }
+    })
+}
+
+/// Compares `self` with `NULL` field by field, adding the bounds this needs to `generics`.
+///
+/// `f32` and `f64` fields are compared bitwise, so that a NaN null value is still found. Other fields are compared with `PartialEq`.
+fn null_eq(data: &Data, generics: &mut Generics) -> TokenStream2 {
+    let null = quote!(<Self as ::inline_option::Nullable>::NULL);
+    match data {
+        Data::Struct(data) => {
+            let (pattern, null_pattern, eq) = fields_eq(&data.fields, generics);
+            quote!({
+                let Self #pattern = self;
+                let Self #null_pattern = &#null;
+                #eq
+            })
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let (pattern, null_pattern, eq) = fields_eq(&variant.fields, generics);
+                arms.push(quote!((Self::#ident #pattern, Self::#ident #null_pattern) => #eq,));
+            }
+            // With a single variant, a catch-all arm would be unreachable.
+            let other = (data.variants.len() > 1).then(|| quote!(_ => false,));
+            quote!(match (self, &#null) {
+                #(#arms)*
+                #other
+            })
+        }
+        Data::Union(_) => {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(Self: ::core::cmp::PartialEq));
+            quote!(::core::cmp::PartialEq::eq(self, &#null))
+        }
+    }
+}
+
+/// Builds the patterns binding the fields of a value and of `NULL`, and the expression comparing them.
+fn fields_eq(
+    fields: &Fields,
+    generics: &mut Generics,
+) -> (TokenStream2, TokenStream2, TokenStream2) {
+    let params: Vec<Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let mut pattern = Vec::new();
+    let mut null_pattern = Vec::new();
+    let mut eqs = Vec::new();
+    for (index, (member, field)) in fields.members().zip(fields).enumerate() {
+        let value = format_ident!("value_{}", index);
+        let null = format_ident!("null_{}", index);
+        pattern.push(quote!(#member: #value));
+        null_pattern.push(quote!(#member: #null));
+
+        let ty = &field.ty;
+        if matches!(primitive(ty).as_deref(), Some("f32" | "f64")) {
+            eqs.push(quote!(#value.to_bits() == #null.to_bits()));
+        } else {
+            // Bounds on concrete types are either trivially true or a clear error, so only generic ones are added.
+            if mentions_any(ty.to_token_stream(), &params) {
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(syn::parse_quote!(#ty: ::core::cmp::PartialEq));
+            }
+            eqs.push(quote!(::core::cmp::PartialEq::eq(#value, #null)));
+        }
+    }
+    let eq = if eqs.is_empty() {
+        quote!(true)
+    } else {
+        quote!(#(#eqs)&&*)
+    };
+    (
+        quote!({ #(#pattern),* }),
+        quote!({ #(#null_pattern),* }),
+        eq,
+    )
+}
+
+/// Returns `true` if `tokens` mention any of the identifiers in `idents`.
+fn mentions_any(tokens: TokenStream2, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Returns the name of a primitive type written as a literal path, such as `u32` or `core::primitive::u32`.
+fn primitive(ty: &Type) -> Option<String> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segments: Vec<String> = path
+        .path
+        .segments
+        .iter()
+        .map(|segment| {
+            segment
+                .arguments
+                .is_none()
+                .then(|| segment.ident.to_string())
+        })
+        .collect::<Option<_>>()?;
+    let name = match segments.as_slice() {
+        [name] if path.path.leading_colon.is_none() => name,
+        [krate, module, name] if (krate == "core" || krate == "std") && module == "primitive" => {
+            name
+        }
+        _ => return None,
+    };
+    matches!(
+        name.as_str(),
+        "u8" | "u16"
+            | "u32"
+            | "u64"
+            | "u128"
+            | "usize"
+            | "i8"
+            | "i16"
+            | "i32"
+            | "i64"
+            | "i128"
+            | "isize"
+            | "f32"
+            | "f64"
+            | "bool"
+            | "char"
+    )
+    .then(|| name.clone())
+}
+
+/// The arguments of a `#[nullable(...)]` attribute.
+#[derive(Default)]
+struct Args {
+    null: Option<Expr>,
+    via: Option<Member>,
+}
+
+/// Parses `#[nullable(null = <expr>)]` and `#[nullable(via = <field>)]`.
+fn parse_args(attrs: &[Attribute]) -> syn::Result<Args> {
+    let mut args = Args::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("nullable")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("null") {
+                if args.null.is_some() {
+                    return Err(meta.error("duplicate `null` argument"));
+                }
+                args.null = Some(meta.value()?.parse::<Expr>()?);
+                Ok(())
+            } else if meta.path.is_ident("via") {
+                if args.via.is_some() {
+                    return Err(meta.error("duplicate `via` argument"));
+                }
+                args.via = Some(meta.value()?.parse::<Member>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported `nullable` argument"))
+            }
+        })?;
+    }
+    Ok(args)
+}
+
+/// Rejects a `#[nullable]` attribute in `attrs`, for places where the current mode would ignore it.
+fn reject_attr(attrs: &[Attribute], message: &str) -> syn::Result<()> {
+    match attrs.iter().find(|attr| attr.path().is_ident("nullable")) {
+        Some(attr) => Err(syn::Error::new_spanned(attr, message)),
+        None => Ok(()),
+    }
+}
+
+/// Rejects `#[nullable]` attributes on fields, which only `via` reads.
+fn reject_field_attrs<'a>(fields: impl IntoIterator<Item = &'a Field>) -> syn::Result<()> {
+    fields.into_iter().try_for_each(|field| {
+        reject_attr(
+            &field.attrs,
+            "`#[nullable]` on a field is only supported with `#[nullable(via = ...)]`",
+        )
+    })
+}
+
+/// Rejects `#[nullable]` attributes on variants and fields when the null value is given by `#[nullable(null = ...)]`.
+fn reject_inner_attrs(data: &Data) -> syn::Result<()> {
+    match data {
+        Data::Struct(data) => reject_field_attrs(&data.fields),
+        Data::Enum(data) => data.variants.iter().try_for_each(|variant| {
+            reject_attr(
+                &variant.attrs,
+                "`#[nullable]` on a variant cannot be combined with `#[nullable(null = ...)]`",
+            )?;
+            reject_field_attrs(&variant.fields)
+        }),
+        Data::Union(data) => reject_field_attrs(&data.fields.named),
+    }
+}
+
+/// Builds the null value from the `via` field's own null value, and delegates the null check to that field.
+///
+/// The other fields take the value of their `#[nullable(null = ...)]` attribute, or the `Default` value of `()` and primitive types.
+fn struct_via(data: &DataStruct, via: &Member) -> syn::Result<(Expr, TokenStream2)> {
+    let members: Vec<Member> = data.fields.members().collect();
+    if !members.contains(via) {
+        return Err(syn::Error::new(via.span(), "no such field"));
+    }
+
+    let mut inits = Vec::new();
+    for (member, field) in members.into_iter().zip(&data.fields) {
+        let ty = &field.ty;
+        let args = parse_args(&field.attrs)?;
+        if let Some(via) = args.via {
+            return Err(syn::Error::new(
+                via.span(),
+                "`via` is only supported on the struct itself",
+            ));
+        }
+
+        let value = if member == *via {
+            if args.null.is_some() {
+                return Err(syn::Error::new(
+                    field.span(),
+                    "the `via` field always uses its own null value",
+                ));
+            }
+            quote!(<#ty as ::inline_option::Nullable>::NULL)
+        } else if let Some(null) = args.null {
+            quote!(#null)
+        } else if let Some(default) = const_default(ty) {
+            default
+        } else {
+            return Err(syn::Error::new_spanned(
+                field,
+                "add `#[nullable(null = ...)]` to this field; only `()` and primitive types \
+                 written as such, like `u32`, default to their `Default` value",
+            ));
+        };
+        inits.push(quote!(#member: #value));
+    }
+
+    Ok((
+        syn::parse_quote!(Self { #(#inits),* }),
+        quote!(::inline_option::Nullable::is_null(&self.#via)),
+    ))
+}
+
+/// Returns the `Default` value of `()` and the primitive types written as literal paths, as a constant expression.
+///
+/// Other types, even ones whose name looks familiar, may be aliases or unrelated types, so they need an explicit value.
+fn const_default(ty: &Type) -> Option<TokenStream2> {
+    if let Type::Tuple(tuple) = ty {
+        return tuple.elems.is_empty().then(|| quote!(()));
+    }
+    let default = match primitive(ty)?.as_str() {
+        "f32" | "f64" => quote!(0.0),
+        "bool" => quote!(false),
+        "char" => quote!('\0'),
+        _ => quote!(0),
+    };
+    Some(default)
+}
+
+/// Finds the single unit variant marked `#[nullable]` and builds both the null value and the null check from it.
+fn enum_null(name: &Ident, data: &DataEnum) -> syn::Result<(Expr, TokenStream2)> {
+    for variant in &data.variants {
+        reject_field_attrs(&variant.fields)?;
+    }
+
+    let mut marked = data.variants.iter().filter(|variant| {
+        variant
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("nullable"))
+    });
+
+    let Some(variant) = marked.next() else {
+        return Err(syn::Error::new(
+            name.span(),
+            "expected `#[nullable]` on one unit variant, or `#[nullable(null = ...)]` on the enum",
+        ));
+    };
+    if let Some(other) = marked.next() {
+        return Err(syn::Error::new(
+            other.span(),
+            "only one variant can be marked `#[nullable]`",
+        ));
+    }
+    if !matches!(variant.fields, Fields::Unit) {
+        return Err(syn::Error::new(
+            variant.span(),
+            "the `#[nullable]` variant must be a unit variant",
+        ));
+    }
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("nullable"))
+    {
+        attr.meta.require_path_only()?;
+    }
+
+    let ident = &variant.ident;
+    Ok((
+        syn::parse_quote!(Self::#ident),
+        quote!(::core::matches!(self, Self::#ident)),
+    ))
+}
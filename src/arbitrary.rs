@@ -0,0 +1,147 @@
+//! [`arbitrary`] support for [`IOption`] and [`Nullable`] values, for fuzzing.
+//!
+//! The [`Arbitrary`] impl of `IOption` never produces a `Some` holding the null value: a generated value that turns out to be null
+//! simply becomes `None`. The helpers in this module give finer control over how often `None` is generated, and generate
+//! bare values that are guaranteed not to be null.
+//!
+//! ```rust
+//! use arbitrary::Unstructured;
+//! use inline_option::{IOption, policy::Max};
+//!
+//! let mut u = Unstructured::new(b"\xff\xff\xff\xff\x2a\0\0\0 and the rest of the fuzzer input");
+//! // The first draw is `u32::MAX`, so it is skipped.
+//! let id: u32 = inline_option::arbitrary::non_null_with_policy::<u32, Max>(&mut u).unwrap();
+//! assert_eq!(id, 42);
+//!
+//! let id: IOption<u32, Max> = inline_option::arbitrary::with_none_ratio(&mut u, 1, 10).unwrap();
+//! assert!(id.as_ref() != Some(&u32::MAX));
+//! ```
+
+use ::arbitrary::{Arbitrary, Error, MaxRecursionReached, Result, Unstructured, size_hint};
+
+use crate::{DefaultPolicy, IOption, NullPolicy, Nullable};
+
+/// How many values [`non_null`] draws before giving up.
+const MAX_ATTEMPTS: usize = 8;
+
+/// Generates an arbitrary value of `T` that is not null.
+///
+/// # Errors
+///
+/// Returns [`Error::IncorrectFormat`] if every one of a few attempts produces the null value,
+/// which happens when the input runs out and `T`'s fallback value is null.
+pub fn non_null<'a, T: Nullable + Arbitrary<'a>>(u: &mut Unstructured<'a>) -> Result<T> {
+    non_null_with_policy::<T, DefaultPolicy>(u)
+}
+
+/// Generates an arbitrary value of `T` that is not null under the policy `P`.
+///
+/// # Errors
+///
+/// See [`non_null`].
+pub fn non_null_with_policy<'a, T: Arbitrary<'a>, P: NullPolicy<T>>(
+    u: &mut Unstructured<'a>,
+) -> Result<T> {
+    for _ in 0..MAX_ATTEMPTS {
+        let value = T::arbitrary(u)?;
+        if !P::is_null(&value) {
+            return Ok(value);
+        }
+    }
+    Err(Error::IncorrectFormat)
+}
+
+/// Generates an arbitrary `IOption`, which is `None` with a probability of `numerator / denominator`.
+///
+/// # Errors
+///
+/// Returns an error if the value of a `Some` cannot be generated (see [`non_null`]).
+///
+/// # Panics
+///
+/// Panics if `numerator` is greater than `denominator`.
+pub fn with_none_ratio<'a, T: Arbitrary<'a>, P: NullPolicy<T>>(
+    u: &mut Unstructured<'a>,
+    numerator: u8,
+    denominator: u8,
+) -> Result<IOption<T, P>> {
+    if numerator > 0 && u.ratio(numerator, denominator)? {
+        Ok(IOption::none_with_policy())
+    } else {
+        non_null_with_policy::<T, P>(u).map(IOption::new_with_policy)
+    }
+}
+
+/// Generates `None` half of the time, like `Option`. A generated value that is null also becomes `None`.
+impl<'a, T: Arbitrary<'a>, P: NullPolicy<T>> Arbitrary<'a> for IOption<T, P> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        if bool::arbitrary(u)? {
+            T::arbitrary(u).map(IOption::new_with_policy)
+        } else {
+            Ok(IOption::none_with_policy())
+        }
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    #[inline]
+    fn try_size_hint(
+        depth: usize,
+    ) -> core::result::Result<(usize, Option<usize>), MaxRecursionReached> {
+        Ok(size_hint::and(
+            bool::try_size_hint(depth)?,
+            size_hint::or((0, Some(0)), T::try_size_hint(depth)?),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::{Max, Zero};
+
+    #[test]
+    fn test_never_some_null() {
+        let data = [0xff; 256];
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let id = IOption::<u32, Max>::arbitrary(&mut u).unwrap();
+            assert_ne!(id.as_ref(), Some(&u32::MAX));
+        }
+    }
+
+    #[test]
+    fn test_non_null() {
+        let data = [0xff; 4]
+            .into_iter()
+            .chain([1, 0, 0, 0])
+            .collect::<std::vec::Vec<u8>>();
+        let mut u = Unstructured::new(&data);
+        assert_eq!(non_null_with_policy::<u32, Max>(&mut u).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_non_null_exhausted() {
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(
+            non_null_with_policy::<u32, Zero>(&mut u).unwrap_err(),
+            Error::IncorrectFormat
+        );
+    }
+
+    #[test]
+    fn test_with_none_ratio() {
+        let data = [0x00; 64];
+        let mut u = Unstructured::new(&data);
+        let id = with_none_ratio::<u32, Max>(&mut u, 1, 1).unwrap();
+        assert!(id.is_none());
+
+        let data = [0x2a; 64];
+        let mut u = Unstructured::new(&data);
+        let id = with_none_ratio::<u32, Max>(&mut u, 0, 1).unwrap();
+        assert!(id.is_some());
+    }
+}
@@ -0,0 +1,163 @@
+//! Conversions between slices of [`IOption`] and Arrow [`PrimitiveArray`]s.
+//!
+//! Arrow stores nullability in a separate validity bitmap, so converting an `IOption` column only needs to derive that bitmap
+//! from [`IOption::is_some`]; the values are copied as-is.
+//!
+//! ```rust
+//! # #[cfg(feature = "nullable-core-floats")] {
+//! use arrow_array::{Array, Float64Array};
+//! use inline_option::{IOption, arrow};
+//!
+//! let column = vec![IOption::new(1.5), IOption::none(), IOption::new(2.5)];
+//! let array: Float64Array = arrow::to_array(&column);
+//! assert_eq!(array.null_count(), 1);
+//!
+//! let back: Vec<IOption<f64>> = arrow::from_array(&array).unwrap();
+//! assert_eq!(back, column);
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use arrow_array::{Array, ArrowPrimitiveType, PrimitiveArray};
+use arrow_buffer::{NullBuffer, ScalarBuffer};
+
+use crate::{IOption, NullPolicy};
+
+/// Builds a [`PrimitiveArray`] from a slice of [`IOption`]s, with a validity bitmap marking the `None` elements.
+///
+/// The slots of `None` elements keep the null value, which Arrow ignores.
+/// If no element is `None`, the array has no validity bitmap at all.
+pub fn to_array<A, P>(values: &[IOption<A::Native, P>]) -> PrimitiveArray<A>
+where
+    A: ArrowPrimitiveType,
+    P: NullPolicy<A::Native>,
+{
+    let nulls = values.iter().map(IOption::is_some).collect::<NullBuffer>();
+    let nulls = (nulls.null_count() > 0).then_some(nulls);
+    let values = values
+        .iter()
+        .map(|value| value.0)
+        .collect::<ScalarBuffer<_>>();
+    PrimitiveArray::new(values, nulls)
+}
+
+type FromArrayResult<A, P> = Result<
+    Vec<IOption<<A as ArrowPrimitiveType>::Native, P>>,
+    ArrayCollision<<A as ArrowPrimitiveType>::Native>,
+>;
+
+/// Converts a [`PrimitiveArray`] into a `Vec` of [`IOption`]s, where the invalid slots become the null value.
+///
+/// # Errors
+///
+/// Returns an [`ArrayCollision`] for the first valid slot that holds the null value, since it would silently turn into `None`.
+pub fn from_array<A, P>(array: &PrimitiveArray<A>) -> FromArrayResult<A, P>
+where
+    A: ArrowPrimitiveType,
+    P: NullPolicy<A::Native>,
+{
+    array
+        .values()
+        .iter()
+        .enumerate()
+        .map(|(index, &value)| {
+            if array.is_null(index) {
+                Ok(IOption::none_with_policy())
+            } else if P::is_null(&value) {
+                Err(ArrayCollision { index, value })
+            } else {
+                Ok(IOption(value, PhantomData))
+            }
+        })
+        .collect()
+}
+
+/// The error returned by [`from_array`] when a valid slot of an Arrow array holds the null value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArrayCollision<T> {
+    /// The index of the offending slot.
+    pub index: usize,
+    /// The value of the offending slot.
+    pub value: T,
+}
+
+impl<T> fmt::Display for ArrayCollision<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "value at index {} collides with the null value of `IOption`",
+            self.index
+        )
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for ArrayCollision<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{string::ToString, vec};
+
+    use arrow_array::{Array, Float32Array, UInt32Array, types::UInt32Type};
+
+    use super::*;
+    use crate::policy::{Max, Nan};
+
+    type Id = IOption<u32, Max>;
+
+    #[test]
+    fn test_to_array() {
+        let ids = [
+            Id::new_with_policy(1),
+            Id::none_with_policy(),
+            Id::new_with_policy(3),
+        ];
+        let array: UInt32Array = to_array(&ids);
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.null_count(), 1);
+        assert!(array.is_valid(0));
+        assert!(array.is_null(1));
+        assert_eq!(array.value(2), 3);
+    }
+
+    #[test]
+    fn test_to_array_no_nulls() {
+        let ids = [Id::new_with_policy(1), Id::new_with_policy(2)];
+        let array = to_array::<UInt32Type, _>(&ids);
+        assert!(array.nulls().is_none());
+    }
+
+    #[test]
+    fn test_from_array() {
+        let array = UInt32Array::from(vec![Some(1), None, Some(3)]);
+        let ids: Vec<Id> = from_array(&array).unwrap();
+        assert_eq!(ids, [Some(1), None, Some(3)]);
+        assert_eq!(to_array(&ids), array);
+    }
+
+    #[test]
+    fn test_from_array_collision() {
+        let array = UInt32Array::from(vec![Some(1), None, Some(u32::MAX)]);
+        let error = from_array::<_, Max>(&array).unwrap_err();
+        assert_eq!(
+            error,
+            ArrayCollision {
+                index: 2,
+                value: u32::MAX
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "value at index 2 collides with the null value of `IOption`"
+        );
+    }
+
+    #[test]
+    fn test_floats() {
+        let array = Float32Array::from(vec![Some(f32::NAN), None]);
+        let values: Vec<IOption<f32, Nan>> = from_array(&array).unwrap();
+        assert!(values[0].is_some());
+        assert!(values[1].is_none());
+    }
+}
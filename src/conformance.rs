@@ -0,0 +1,113 @@
+//! The [`nullable_conformance_tests!`](crate::nullable_conformance_tests) macro.
+
+/// Generates `#[test]` functions checking that a [`Nullable`](crate::Nullable) impl upholds the rules of the trait.
+///
+/// The generated tests check that:
+///
+/// - `NULL.is_null()` holds.
+/// - None of the samples is null, or equal to `NULL`.
+/// - `IOption<T>` has the same size and alignment as `T`.
+/// - Storing each sample with `replace` and getting it back with `take` leaves it intact.
+///
+/// The type must implement `PartialEq`. The samples should cover the values closest to the null value, as well as any
+/// values that are easy to confuse with it, such as `-0.0` for `0.0`. Each sample expression is evaluated once per use,
+/// so it must be cheap and deterministic.
+///
+/// The tests have fixed names, so the macro can be invoked at most once per module:
+///
+/// ```rust
+/// use inline_option::Nullable;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct NodeId(u32);
+///
+/// impl Nullable for NodeId {
+///     const NULL: Self = NodeId(u32::MAX);
+///
+///     fn is_null(&self) -> bool {
+///         self.0 == u32::MAX
+///     }
+/// }
+///
+/// mod tests {
+///     use super::*;
+///
+///     inline_option::nullable_conformance_tests!(NodeId, samples = [NodeId(0), NodeId(u32::MAX - 1)]);
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! nullable_conformance_tests {
+    ($ty:ty, samples = [$($sample:expr),* $(,)?] $(,)?) => {
+        // Type-checks the arguments even where the tests are not compiled, such as in doctests.
+        const _: fn() = || {
+            fn check<T: $crate::Nullable + ::core::cmp::PartialEq>(_: &[T]) {}
+            check::<$ty>(&[$($sample),*]);
+        };
+
+        #[test]
+        fn nullable_null_is_null() {
+            ::core::assert!(
+                $crate::Nullable::is_null(&<$ty as $crate::Nullable>::NULL),
+                "`NULL.is_null()` returned false",
+            );
+            ::core::assert!(
+                $crate::IOption::<$ty>::none().is_none(),
+                "`IOption::none()` is not `None`",
+            );
+        }
+
+        #[test]
+        fn nullable_samples_are_not_null() {
+            $(
+                ::core::assert!(
+                    !$crate::Nullable::is_null(&$sample),
+                    "sample `{}` is null",
+                    ::core::stringify!($sample),
+                );
+                ::core::assert!(
+                    $sample != <$ty as $crate::Nullable>::NULL,
+                    "sample `{}` is not null, but equals `NULL`",
+                    ::core::stringify!($sample),
+                );
+            )*
+        }
+
+        #[test]
+        fn nullable_layout() {
+            ::core::assert_eq!(
+                ::core::mem::size_of::<$crate::IOption<$ty>>(),
+                ::core::mem::size_of::<$ty>(),
+                "`IOption` has a different size",
+            );
+            ::core::assert_eq!(
+                ::core::mem::align_of::<$crate::IOption<$ty>>(),
+                ::core::mem::align_of::<$ty>(),
+                "`IOption` has a different alignment",
+            );
+        }
+
+        #[test]
+        fn nullable_take_replace_roundtrip() {
+            $(
+                let mut ioption = $crate::IOption::<$ty>::none();
+                ::core::assert!(
+                    ioption.replace($sample).is_none(),
+                    "`replace` on `None` returned `Some`",
+                );
+                ::core::assert!(
+                    ioption.is_some(),
+                    "sample `{}` is `None` after `replace`",
+                    ::core::stringify!($sample),
+                );
+                let taken = ioption.take();
+                ::core::assert!(ioption.is_none(), "`take` did not leave `None` behind");
+                ::core::assert!(
+                    taken.into_inner() == $sample,
+                    "sample `{}` changed in a `replace`/`take` round trip",
+                    ::core::stringify!($sample),
+                );
+            )*
+        }
+    };
+}
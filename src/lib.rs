@@ -1,4 +1,93 @@
 #![doc = include_str!("../README.md")]
+#![no_std]
+#![cfg_attr(feature = "nightly", feature(try_trait_v2))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod conformance;
+pub mod nan;
+pub mod num;
+pub mod policy;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde")]
+pub mod serde;
+
+pub use policy::{DefaultPolicy, NullPolicy};
+
+/// Derive macro generating an impl of the trait [`Nullable`].
+///
+/// The null value is given either as an expression on the type, or by marking one unit variant of an enum:
+///
+/// ```rust
+/// use inline_option::Nullable;
+///
+/// #[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+/// #[nullable(null = Value(i32::MAX))]
+/// struct Value(i32);
+///
+/// #[derive(Nullable, Clone, Copy, Debug)]
+/// enum Slot {
+///     Occupied(u16),
+///     #[nullable]
+///     Empty,
+/// }
+///
+/// assert!(Value(i32::MAX).is_null());
+/// assert!(Slot::Empty.is_null());
+/// assert!(!Slot::Occupied(3).is_null());
+/// ```
+///
+/// With `#[nullable(null = ...)]`, the generated `is_null` compares each field with the same field of `NULL`. `f32` and `f64` fields
+/// are compared bitwise, so a NaN in `NULL` is still found, and the other fields with [`PartialEq`]. A field of any other type
+/// must therefore equal itself in `NULL`, or `NULL` would not be null.
+///
+/// A struct can also delegate to one of its fields that is already [`Nullable`] with `#[nullable(via = field)]`.
+/// The struct is null exactly when that field is null, and its `NULL` is built from the field's `NULL`.
+/// The other fields are set to their `#[nullable(null = ...)]` value. Since `NULL` is a constant, only fields of type `()` or of
+/// a primitive type written as such, like `u32` or `core::primitive::u32` but not an alias of it, may leave it out and get their
+/// [`Default`] value.
+///
+/// ```rust
+/// use inline_option::{IOption, Nullable};
+///
+/// #[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+/// #[nullable(null = Id(u32::MAX))]
+/// struct Id(u32);
+///
+/// #[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+/// #[nullable(via = id)]
+/// struct Handle {
+///     id: Id,
+///     #[nullable(null = 1)]
+///     generation: u16,
+/// }
+///
+/// assert_eq!(Handle::NULL, Handle { id: Id::NULL, generation: 1 });
+/// assert!(Handle { id: Id::NULL, generation: 7 }.is_null());
+/// assert_eq!(size_of::<IOption<Handle>>(), size_of::<Handle>());
+/// ```
+#[cfg(feature = "derive")]
+pub use inline_option_derive::Nullable;
 
 /// A trait for defining nullable values.
 pub trait Nullable {
@@ -36,23 +125,45 @@ impl<T> Nullable for *mut T {
     }
 }
 
+/// A pair is null if either of its elements is null. This is what [`IOption::zip`] relies on.
+impl<A: Nullable, B: Nullable> Nullable for (A, B) {
+    const NULL: Self = (A::NULL, B::NULL);
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        self.0.is_null() || self.1.is_null()
+    }
+}
+
+/// An `IOption` is null if it is `None`, so `IOption`s can be nested without growing.
+///
+/// The price is that the outer `IOption` cannot hold an inner `None`: `Some(None)` is the null value, and reads back as `None`.
+impl<T, P: NullPolicy<T>> Nullable for IOption<T, P> {
+    const NULL: Self = IOption::none_with_policy();
+
+    #[inline]
+    fn is_null(&self) -> bool {
+        self.is_none()
+    }
+}
+
 #[cfg(feature = "nullable-core-floats")]
 impl Nullable for f32 {
-    const NULL: Self = f32::MAX;
+    const NULL: Self = nan::F32_NULL;
 
     #[inline]
     fn is_null(&self) -> bool {
-        *self == f32::MAX
+        nan::is_null_f32(*self)
     }
 }
 
 #[cfg(feature = "nullable-core-floats")]
 impl Nullable for f64 {
-    const NULL: Self = f64::MAX;
+    const NULL: Self = nan::F64_NULL;
 
     #[inline]
     fn is_null(&self) -> bool {
-        *self == f64::MAX
+        nan::is_null_f64(*self)
     }
 }
 
@@ -178,33 +289,274 @@ impl Nullable for usize {
 
 /// The `IOption` type, a transparent newtype wrapper around a [`Nullable`] value that provides a similar API to [`Option`][core::option::Option].
 ///
+/// The second type parameter is the [`NullPolicy`] deciding which value of `T` is null. By default, it is the [`Nullable`] impl of `T`.
+/// Combinators that produce a new `IOption` keep the policy of `self`.
+///
 /// See the [module-level documentation](crate) for more information.
-#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(transparent)]
-pub struct IOption<T: Nullable>(T);
+pub struct IOption<T, P: NullPolicy<T> = DefaultPolicy>(T, PhantomData<fn() -> P>);
+
+impl<T, P: NullPolicy<T>> Default for IOption<T, P> {
+    #[inline]
+    fn default() -> Self {
+        Self::none_with_policy()
+    }
+}
+
+/// Formats as `None` or `Some(..)`, exactly like [`Option`], instead of showing the null value.
+impl<T: fmt::Debug, P: NullPolicy<T>> fmt::Debug for IOption<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.as_ref() {
+            Some(value) => f.debug_tuple("Some").field(value).finish(),
+            None => f.write_str("None"),
+        }
+    }
+}
+
+/// Formats the contained value, or `None`. Use [`IOption::display_or`] for a different placeholder.
+impl<T: fmt::Display, P: NullPolicy<T>> fmt::Display for IOption<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_or("None"), f)
+    }
+}
+
+/// Displays an [`IOption`] with a placeholder for `None`. Created by [`IOption::display_or`].
+pub struct DisplayOr<'a, T, P: NullPolicy<T>> {
+    ioption: &'a IOption<T, P>,
+    placeholder: &'a str,
+}
+
+impl<T: fmt::Display, P: NullPolicy<T>> fmt::Display for DisplayOr<'_, T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.ioption.as_ref() {
+            Some(value) => fmt::Display::fmt(value, f),
+            None => f.pad(self.placeholder),
+        }
+    }
+}
+
+impl<T: Clone, P: NullPolicy<T>> Clone for IOption<T, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+
+impl<T: Copy, P: NullPolicy<T>> Copy for IOption<T, P> {}
+
+/// Compares like [`Option`]: all null values are equal to each other, even if they have different representations.
+impl<T: PartialEq, P: NullPolicy<T>> PartialEq for IOption<T, P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T: Eq, P: NullPolicy<T>> Eq for IOption<T, P> {}
+
+/// Orders like [`Option`]: `None` is less than any `Some`, regardless of the null value.
+///
+/// Use [`RawOrd`] to compare the inner values directly instead.
+impl<T: PartialOrd, P: NullPolicy<T>> PartialOrd for IOption<T, P> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+/// Orders like [`Option`]: `None` is less than any `Some`, regardless of the null value.
+///
+/// Use [`RawOrd`] to compare the inner values directly instead.
+impl<T: Ord, P: NullPolicy<T>> Ord for IOption<T, P> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(&other.as_ref())
+    }
+}
+
+impl<T: PartialEq, P: NullPolicy<T>> PartialEq<Option<T>> for IOption<T, P> {
+    #[inline]
+    fn eq(&self, other: &Option<T>) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T: PartialEq, P: NullPolicy<T>> PartialEq<IOption<T, P>> for Option<T> {
+    #[inline]
+    fn eq(&self, other: &IOption<T, P>) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+/// Compares with a bare value as if it were a `Some`, so a `None` is never equal to any value.
+impl<T: PartialEq, P: NullPolicy<T>> PartialEq<T> for IOption<T, P> {
+    #[inline]
+    fn eq(&self, other: &T) -> bool {
+        self.as_ref() == Some(other)
+    }
+}
+
+impl<T: PartialOrd, P: NullPolicy<T>> PartialOrd<Option<T>> for IOption<T, P> {
+    #[inline]
+    fn partial_cmp(&self, other: &Option<T>) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+impl<T: PartialOrd, P: NullPolicy<T>> PartialOrd<IOption<T, P>> for Option<T> {
+    #[inline]
+    fn partial_cmp(&self, other: &IOption<T, P>) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&other.as_ref())
+    }
+}
+
+/// Compares with a bare value as if it were a `Some`, so a `None` is less than any value.
+impl<T: PartialOrd, P: NullPolicy<T>> PartialOrd<T> for IOption<T, P> {
+    #[inline]
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.as_ref().partial_cmp(&Some(other))
+    }
+}
+
+/// A wrapper that compares [`IOption`]s by their inner values, including the null value, without checking for `None`.
+///
+/// This is faster than the [`Option`]-like ordering of `IOption`, but where `None` ends up depends on the null value.
+/// For example, with `u32::MAX` as null, `None` sorts after every `Some`:
+///
+/// ```rust
+/// use inline_option::{IOption, RawOrd, policy::Max};
+///
+/// let mut values = [
+///     IOption::<u32, Max>::none_with_policy(),
+///     IOption::new_with_policy(1),
+/// ];
+/// values.sort_by_key(|value| RawOrd(*value));
+/// assert!(values[1].is_none());
+/// ```
+#[repr(transparent)]
+pub struct RawOrd<T, P: NullPolicy<T> = DefaultPolicy>(pub IOption<T, P>);
 
-impl<T: Nullable> Default for IOption<T> {
+impl<T, P: NullPolicy<T>> Default for RawOrd<T, P> {
     #[inline]
     fn default() -> Self {
-        Self::new(T::NULL)
+        Self(IOption::default())
+    }
+}
+
+impl<T: fmt::Debug, P: NullPolicy<T>> fmt::Debug for RawOrd<T, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawOrd").field(&self.0).finish()
+    }
+}
+
+impl<T: Clone, P: NullPolicy<T>> Clone for RawOrd<T, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: Copy, P: NullPolicy<T>> Copy for RawOrd<T, P> {}
+
+impl<T: PartialEq, P: NullPolicy<T>> PartialEq for RawOrd<T, P> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.0 == other.0.0
+    }
+}
+
+impl<T: Eq, P: NullPolicy<T>> Eq for RawOrd<T, P> {}
+
+impl<T: PartialOrd, P: NullPolicy<T>> PartialOrd for RawOrd<T, P> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.0.partial_cmp(&other.0.0)
+    }
+}
+
+impl<T: Ord, P: NullPolicy<T>> Ord for RawOrd<T, P> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.0.cmp(&other.0.0)
+    }
+}
+
+/// Hashes exactly like the equivalent [`Option`], so all null values hash the same.
+impl<T: Hash, P: NullPolicy<T>> Hash for IOption<T, P> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state);
     }
 }
 
 impl<T: Nullable> IOption<T> {
+    /// Wraps `value` as-is, so the null value becomes `None`.
+    ///
+    /// Since this is a `const fn`, it cannot check for collisions, even with the `strict` feature. Use [`IOption::try_some`] for that.
     #[inline]
     pub const fn new(value: T) -> Self {
-        Self(value)
+        Self::new_with_policy(value)
     }
 
     #[inline]
     pub const fn none() -> Self {
-        Self(T::NULL)
+        Self::none_with_policy()
+    }
+
+    /// Creates a `Some`, or returns an error if `value` is the null value.
+    ///
+    /// Unlike [`IOption::new`], this can never silently produce a `None`.
+    #[inline]
+    pub fn try_some(value: T) -> Result<Self, SentinelCollision<T>> {
+        Self::try_some_with_policy(value)
+    }
+}
+
+impl<T, P: NullPolicy<T>> IOption<T, P> {
+    /// The `None` value, usable in `const` contexts and array initializers:
+    ///
+    /// ```rust
+    /// use inline_option::{IOption, policy::Max};
+    ///
+    /// static TABLE: [IOption<u32, Max>; 256] = [IOption::NONE; 256];
+    /// const _: () = assert!(TABLE[0].const_is_none());
+    /// assert!(TABLE.iter().all(IOption::is_none));
+    /// ```
+    pub const NONE: Self = Self::none_with_policy();
+
+    /// Like [`IOption::new`], but for any policy.
+    #[inline]
+    pub const fn new_with_policy(value: T) -> Self {
+        Self(value, PhantomData)
+    }
+
+    /// Like [`IOption::none`], but for any policy.
+    #[inline]
+    pub const fn none_with_policy() -> Self {
+        Self(P::NULL, PhantomData)
+    }
+
+    /// Like [`IOption::try_some`], but for any policy.
+    #[inline]
+    pub fn try_some_with_policy(value: T) -> Result<Self, SentinelCollision<T>> {
+        SentinelCollision::check::<P>(value).map(Self::new_with_policy)
+    }
+
+    /// Converts to another policy. A `None` becomes the null value of the new policy, while a `Some` keeps its value.
+    ///
+    /// If the value is null under the new policy, the result is `None`.
+    #[inline]
+    pub fn with_policy<Q: NullPolicy<T>>(self) -> IOption<T, Q> {
+        if self.is_none() {
+            IOption::none_with_policy()
+        } else {
+            IOption::new_with_policy(self.into_inner())
+        }
     }
 
     #[inline]
     pub fn is_none(&self) -> bool {
-        self.0.is_null()
+        P::is_null(&self.0)
     }
 
     #[inline]
@@ -213,8 +565,35 @@ impl<T: Nullable> IOption<T> {
     }
 
     #[inline]
-    pub fn into_inner(self) -> T {
-        self.0
+    pub fn is_some_and<F>(self, f: F) -> bool
+    where
+        F: FnOnce(T) -> bool,
+    {
+        if self.is_none() {
+            false
+        } else {
+            f(self.into_inner())
+        }
+    }
+
+    #[inline]
+    pub fn is_none_or<F>(self, f: F) -> bool
+    where
+        F: FnOnce(T) -> bool,
+    {
+        if self.is_none() {
+            true
+        } else {
+            f(self.into_inner())
+        }
+    }
+
+    #[inline]
+    pub const fn into_inner(self) -> T {
+        // A plain `self.0` would make the compiler drop the rest of `self`, which a generic `const fn` cannot do.
+        let this = core::mem::ManuallyDrop::new(self);
+        // SAFETY: `IOption` and `ManuallyDrop` are both `#[repr(transparent)]`, so `this` is a `T`, and it is never used again.
+        unsafe { core::ptr::read((&raw const this).cast::<T>()) }
     }
 
     #[inline]
@@ -232,22 +611,136 @@ impl<T: Nullable> IOption<T> {
     }
 
     #[inline]
-    pub fn map<U, F>(self, f: F) -> IOption<U>
+    pub fn as_pin_ref(self: Pin<&Self>) -> Option<Pin<&T>> {
+        // SAFETY: `IOption` never moves its value out from behind a pinned reference, and has no `Drop` impl.
+        self.get_ref()
+            .as_ref()
+            .map(|value| unsafe { Pin::new_unchecked(value) })
+    }
+
+    #[inline]
+    pub fn as_pin_mut(self: Pin<&mut Self>) -> Option<Pin<&mut T>> {
+        // SAFETY: `IOption` never moves its value out from behind a pinned reference, and has no `Drop` impl.
+        unsafe {
+            self.get_unchecked_mut()
+                .as_mut()
+                .map(|value| Pin::new_unchecked(value))
+        }
+    }
+
+    /// Returns a slice of the contained value, which is empty if `self` is `None`.
+    ///
+    /// The slice always points at the inline value, only its length depends on whether `self` is `Some`.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        let len = self.is_some() as usize;
+        &core::slice::from_ref(&self.0)[..len]
+    }
+
+    /// Returns a mutable slice of the contained value, which is empty if `self` is `None`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let len = self.is_some() as usize;
+        &mut core::slice::from_mut(&mut self.0)[..len]
+    }
+
+    /// Views a reference to a raw value as a reference to an `IOption`, which is `None` if the value is null.
+    ///
+    /// The policy is usually inferred from the expected type:
+    ///
+    /// ```rust
+    /// use inline_option::{IOption, policy::Max};
+    ///
+    /// let index = u32::MAX;
+    /// let index: &IOption<u32, Max> = IOption::from_ref(&index);
+    /// assert!(index.is_none());
+    /// ```
+    #[inline]
+    pub const fn from_ref(value: &T) -> &Self {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, and every `T` is a valid `IOption`.
+        unsafe { &*(value as *const T as *const Self) }
+    }
+
+    /// Views a mutable reference to a raw value as a mutable reference to an `IOption`.
+    ///
+    /// Storing `None` through it writes the null value to `value`.
+    #[inline]
+    pub const fn from_mut(value: &mut T) -> &mut Self {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, and every `T` is a valid `IOption` and vice versa.
+        unsafe { &mut *(value as *mut T as *mut Self) }
+    }
+
+    /// Views a slice of raw values as a slice of `IOption`s, where the null values are `None`.
+    ///
+    /// ```rust
+    /// use inline_option::{IOption, policy::Max};
+    ///
+    /// let indices = [0, u32::MAX, 2];
+    /// let indices: &[IOption<u32, Max>] = IOption::slice_from_inner(&indices);
+    /// assert!(indices[1].is_none());
+    /// assert_eq!(IOption::slice_as_inner(indices), [0, u32::MAX, 2]);
+    /// ```
+    #[inline]
+    pub const fn slice_from_inner(values: &[T]) -> &[Self] {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, so the slices have the same layout.
+        unsafe { &*(values as *const [T] as *const [Self]) }
+    }
+
+    /// Views a mutable slice of raw values as a mutable slice of `IOption`s.
+    #[inline]
+    pub const fn slice_from_inner_mut(values: &mut [T]) -> &mut [Self] {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, so the slices have the same layout.
+        unsafe { &mut *(values as *mut [T] as *mut [Self]) }
+    }
+
+    /// Views a slice of `IOption`s as a slice of raw values, where `None`s are the null value.
+    #[inline]
+    pub const fn slice_as_inner(values: &[Self]) -> &[T] {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, so the slices have the same layout.
+        unsafe { &*(values as *const [Self] as *const [T]) }
+    }
+
+    /// Views a mutable slice of `IOption`s as a mutable slice of raw values.
+    ///
+    /// Writing the null value through it turns the element into `None`.
+    #[inline]
+    pub const fn slice_as_inner_mut(values: &mut [Self]) -> &mut [T] {
+        // SAFETY: `IOption` is `#[repr(transparent)]` over `T`, so the slices have the same layout.
+        unsafe { &mut *(values as *mut [Self] as *mut [T]) }
+    }
+
+    #[inline]
+    pub fn as_deref(&self) -> Option<&T::Target>
+    where
+        T: Deref,
+    {
+        self.as_ref().map(|value| value.deref())
+    }
+
+    #[inline]
+    pub fn as_deref_mut(&mut self) -> Option<&mut T::Target>
+    where
+        T: DerefMut,
+    {
+        self.as_mut().map(|value| value.deref_mut())
+    }
+
+    #[inline]
+    pub fn map<U, F>(self, f: F) -> IOption<U, P>
     where
-        U: Nullable,
+        P: NullPolicy<U>,
         F: FnOnce(T) -> U,
     {
         if self.is_none() {
-            IOption::new(U::NULL)
+            IOption::none_with_policy()
         } else {
-            IOption::new(f(self.into_inner()))
+            IOption::new_with_policy(f(self.into_inner()))
         }
     }
 
     #[inline]
     pub fn map_or<U, F>(self, default: U, f: F) -> U
     where
-        U: Nullable,
         F: FnOnce(T) -> U,
     {
         if self.is_none() {
@@ -260,7 +753,6 @@ impl<T: Nullable> IOption<T> {
     #[inline]
     pub fn map_or_else<U, D, F>(self, default: D, f: F) -> U
     where
-        U: Nullable,
         D: FnOnce() -> U,
         F: FnOnce(T) -> U,
     {
@@ -271,6 +763,54 @@ impl<T: Nullable> IOption<T> {
         }
     }
 
+    /// Like [`IOption::map`], but returns a standard [`Option`], so `U` does not need to be nullable.
+    #[inline]
+    pub fn map_option<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        if self.is_none() {
+            None
+        } else {
+            Some(f(self.into_inner()))
+        }
+    }
+
+    /// Like [`IOption::and_then`], but for a function returning a standard [`Option`], so `U` does not need to be nullable.
+    #[inline]
+    pub fn and_then_option<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        if self.is_none() {
+            None
+        } else {
+            f(self.into_inner())
+        }
+    }
+
+    /// Keeps the result of `f` if `self` is `Some` and `f` returns `Some`, like [`Iterator::filter_map`] over the contained value.
+    ///
+    /// This is the same as [`IOption::and_then_option`], for pipelines that read better in iterator terms.
+    #[inline]
+    pub fn filter_map_option<U, F>(self, f: F) -> Option<U>
+    where
+        F: FnOnce(T) -> Option<U>,
+    {
+        self.and_then_option(f)
+    }
+
+    #[inline]
+    pub fn inspect<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&T),
+    {
+        if let Some(value) = self.as_ref() {
+            f(value);
+        }
+        self
+    }
+
     #[inline]
     pub fn ok_or<E>(self, err: E) -> Result<T, E> {
         if self.is_none() {
@@ -292,87 +832,159 @@ impl<T: Nullable> IOption<T> {
         }
     }
 
+    /// Converts to a [`Result`] with [`NoneError`] as the error, so `?` can be used in functions returning a `Result`.
+    ///
+    /// This is the same as `self.ok_or(NoneError)`.
+    #[inline]
+    pub fn into_result(self) -> Result<T, NoneError> {
+        self.ok_or(NoneError)
+    }
+
     #[inline]
-    pub fn and<U>(self, other: IOption<U>) -> IOption<U>
+    pub fn and<U>(self, other: IOption<U, P>) -> IOption<U, P>
     where
-        U: Nullable,
+        P: NullPolicy<U>,
     {
         if self.is_none() {
-            IOption::new(U::NULL)
+            IOption::none_with_policy()
         } else {
             other
         }
     }
 
     #[inline]
-    pub fn and_then<U, F>(self, f: F) -> IOption<U>
+    pub fn and_then<U, F>(self, f: F) -> IOption<U, P>
     where
-        U: Nullable,
-        F: FnOnce(T) -> IOption<U>,
+        P: NullPolicy<U>,
+        F: FnOnce(T) -> IOption<U, P>,
     {
         if self.is_none() {
-            IOption::new(U::NULL)
+            IOption::none_with_policy()
         } else {
             f(self.into_inner())
         }
     }
 
     #[inline]
-    pub fn or(self, other: IOption<T>) -> IOption<T>
-    where
-        T: Nullable,
-    {
+    pub fn or(self, other: IOption<T, P>) -> IOption<T, P> {
         if self.is_none() { other } else { self }
     }
 
     #[inline]
-    pub fn or_else<F>(self, f: F) -> IOption<T>
+    pub fn or_else<F>(self, f: F) -> IOption<T, P>
     where
-        T: Nullable,
-        F: FnOnce() -> IOption<T>,
+        F: FnOnce() -> IOption<T, P>,
     {
         if self.is_none() { f() } else { self }
     }
 
     #[inline]
-    #[track_caller]
-    pub fn unwrap(self) -> T {
-        if self.is_none() {
-            panic!("called `IOption::unwrap()` on a `None` value")
-        } else {
-            self.into_inner()
+    pub fn xor(self, other: IOption<T, P>) -> IOption<T, P> {
+        match (self.is_some(), other.is_some()) {
+            (true, false) => self,
+            (false, true) => other,
+            _ => IOption::none_with_policy(),
         }
     }
 
+    /// Zips `self` with another `IOption` into an `IOption` of a pair, which is `None` if either is `None`.
+    ///
+    /// The pair must be nullable under the policy. For [`DefaultPolicy`], that means both `T` and `U` are [`Nullable`].
+    /// Use [`IOption::zip_option`] otherwise.
     #[inline]
-    pub fn unwrap_or(self, default: T) -> T {
-        if self.is_none() {
-            default
-        } else {
-            self.into_inner()
-        }
+    pub fn zip<U>(self, other: IOption<U, P>) -> IOption<(T, U), P>
+    where
+        P: NullPolicy<U> + NullPolicy<(T, U)>,
+    {
+        self.zip_with(other, |a, b| (a, b))
     }
 
     #[inline]
-    pub fn unwrap_or_else<F>(self, f: F) -> T
+    pub fn zip_with<U, R, F>(self, other: IOption<U, P>, f: F) -> IOption<R, P>
     where
-        F: FnOnce() -> T,
+        P: NullPolicy<U> + NullPolicy<R>,
+        F: FnOnce(T, U) -> R,
     {
-        if self.is_none() {
-            f()
+        if self.is_none() || other.is_none() {
+            IOption::none_with_policy()
         } else {
-            self.into_inner()
+            IOption::new_with_policy(f(self.into_inner(), other.into_inner()))
         }
     }
 
+    /// Like [`IOption::zip`], but returns a standard [`Option`], so the pair does not need to be nullable.
     #[inline]
-    #[track_caller]
-    pub fn expect(self, msg: &str) -> T {
-        if self.is_none() {
-            panic!("{}", msg)
-        } else {
-            self.into_inner()
-        }
+    pub fn zip_option<U>(self, other: IOption<U, P>) -> Option<(T, U)>
+    where
+        P: NullPolicy<U>,
+    {
+        self.zip_with_option(other, |a, b| (a, b))
+    }
+
+    /// Like [`IOption::zip_with`], but returns a standard [`Option`], so `R` does not need to be nullable.
+    #[inline]
+    pub fn zip_with_option<U, R, F>(self, other: IOption<U, P>, f: F) -> Option<R>
+    where
+        P: NullPolicy<U>,
+        F: FnOnce(T, U) -> R,
+    {
+        if self.is_none() || other.is_none() {
+            None
+        } else {
+            Some(f(self.into_inner(), other.into_inner()))
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        if self.is_none() {
+            panic!("called `IOption::unwrap()` on a `None` value")
+        } else {
+            self.into_inner()
+        }
+    }
+
+    #[inline]
+    pub fn unwrap_or(self, default: T) -> T {
+        if self.is_none() {
+            default
+        } else {
+            self.into_inner()
+        }
+    }
+
+    #[inline]
+    pub fn unwrap_or_else<F>(self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_none() {
+            f()
+        } else {
+            self.into_inner()
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        if self.is_none() {
+            panic!("{}", msg)
+        } else {
+            self.into_inner()
+        }
+    }
+
+    /// Returns an object that displays the contained value, or `placeholder` if `self` is `None`.
+    ///
+    /// Formatting options such as width and alignment apply to the placeholder too.
+    #[inline]
+    pub fn display_or<'a>(&'a self, placeholder: &'a str) -> DisplayOr<'a, T, P> {
+        DisplayOr {
+            ioption: self,
+            placeholder,
+        }
     }
 
     #[inline]
@@ -386,72 +998,360 @@ impl<T: Nullable> IOption<T> {
     }
 
     #[inline]
-    pub fn filter<P>(self, predicate: P) -> IOption<T>
+    pub fn filter<F>(self, predicate: F) -> IOption<T, P>
     where
-        P: FnOnce(&T) -> bool,
+        F: FnOnce(&T) -> bool,
     {
         let inner = self.into_inner();
-        if inner.is_null() || !predicate(&inner) {
-            IOption::new(T::NULL)
+        if P::is_null(&inner) || !predicate(&inner) {
+            IOption::none_with_policy()
         } else {
-            IOption::new(inner)
+            IOption::new_with_policy(inner)
         }
     }
 
     #[inline]
-    pub fn take(&mut self) -> IOption<T> {
+    pub fn take(&mut self) -> IOption<T, P> {
         core::mem::take(self)
     }
 
     #[inline]
-    pub fn replace(&mut self, value: T) -> IOption<T> {
-        core::mem::replace(self, IOption::new(value))
+    pub fn take_if<F>(&mut self, predicate: F) -> IOption<T, P>
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        if self.as_mut().is_some_and(predicate) {
+            self.take()
+        } else {
+            IOption::none_with_policy()
+        }
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn replace(&mut self, value: T) -> IOption<T, P> {
+        Self::debug_assert_not_null(&value);
+        core::mem::replace(self, IOption::new_with_policy(value))
+    }
+
+    /// Like [`IOption::replace`], but returns an error instead of storing a value that collides with the null value.
+    #[inline]
+    pub fn try_replace(&mut self, value: T) -> Result<IOption<T, P>, SentinelCollision<T>> {
+        SentinelCollision::check::<P>(value).map(|value| self.replace(value))
     }
 
+    /// Stores `value`, then returns a mutable reference to it.
     #[inline]
+    #[track_caller]
+    pub fn insert(&mut self, value: T) -> &mut T {
+        Self::debug_assert_not_null(&value);
+        self.0 = value;
+        &mut self.0
+    }
+
+    /// Inserts `value` if `self` is `None`, then returns a mutable reference to the contained value.
+    ///
+    /// If `value` is the null value, `self` stays `None` and the returned reference points to the null value.
+    #[inline]
+    #[track_caller]
     pub fn get_or_insert(&mut self, value: T) -> &mut T {
         if self.is_none() {
-            *self = IOption::new(value);
+            Self::debug_assert_not_null(&value);
+            self.0 = value;
         }
-        self.as_mut().unwrap()
+        &mut self.0
     }
 
+    /// Like [`IOption::get_or_insert`], but returns an error instead of inserting a value that collides with the null value.
     #[inline]
+    pub fn try_get_or_insert(&mut self, value: T) -> Result<&mut T, SentinelCollision<T>> {
+        if self.is_none() {
+            self.0 = SentinelCollision::check::<P>(value)?;
+        }
+        Ok(&mut self.0)
+    }
+
+    #[inline]
+    #[track_caller]
     pub fn get_or_insert_with<F>(&mut self, f: F) -> &mut T
     where
         F: FnOnce() -> T,
     {
         if self.is_none() {
-            *self = IOption::new(f());
+            let value = f();
+            Self::debug_assert_not_null(&value);
+            self.0 = value;
         }
-        self.as_mut().unwrap()
+        &mut self.0
     }
 
+    /// Like [`IOption::get_or_insert_with`], but returns an error instead of inserting a value that collides with the null value.
     #[inline]
+    pub fn try_get_or_insert_with<F>(&mut self, f: F) -> Result<&mut T, SentinelCollision<T>>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_none() {
+            self.0 = SentinelCollision::check::<P>(f())?;
+        }
+        Ok(&mut self.0)
+    }
+
+    #[inline]
+    #[track_caller]
     pub fn get_or_insert_default(&mut self) -> &mut T
     where
         T: Default,
     {
+        self.get_or_insert_with(T::default)
+    }
+
+    /// With the `strict` feature, asserts in debug builds that `value` is not the null value.
+    #[inline]
+    #[track_caller]
+    fn debug_assert_not_null(value: &T) {
+        debug_assert!(
+            !cfg!(feature = "strict") || !P::is_null(value),
+            "value collides with the null value of `IOption`"
+        );
+    }
+}
+
+impl<'a, T, P> IOption<&'a T, P>
+where
+    P: NullPolicy<&'a T> + NullPolicy<T>,
+{
+    #[inline]
+    pub fn copied(self) -> IOption<T, P>
+    where
+        T: Copy,
+    {
+        self.map(|value| *value)
+    }
+
+    #[inline]
+    pub fn cloned(self) -> IOption<T, P>
+    where
+        T: Clone,
+    {
+        self.map(|value| value.clone())
+    }
+}
+
+/// `const` versions of the basic queries, for the built-in [`Nullable`] impls and [policies](policy) whose null check can run
+/// at compile time.
+///
+/// Trait methods such as [`Nullable::is_null`] cannot be called in `const` contexts yet, so these check the null value directly.
+/// They behave exactly like the methods without the `const_` prefix.
+macro_rules! impl_const_queries {
+    ($($(#[$attr:meta])* impl<$($param:ident),*> for IOption<$ty:ty, $policy:ty>: |$value:ident| $is_null:expr;)*) => {
+        $(
+            $(#[$attr])*
+            impl<$($param),*> IOption<$ty, $policy> {
+                /// Like [`IOption::is_none`], but usable in `const` contexts.
+                #[inline]
+                pub const fn const_is_none(&self) -> bool {
+                    let $value = self.0;
+                    $is_null
+                }
+
+                /// Like [`IOption::is_some`], but usable in `const` contexts.
+                #[inline]
+                pub const fn const_is_some(&self) -> bool {
+                    !self.const_is_none()
+                }
+
+                /// Like [`IOption::unwrap_or`], but usable in `const` contexts.
+                #[inline]
+                pub const fn const_unwrap_or(self, default: $ty) -> $ty {
+                    if self.const_is_none() {
+                        default
+                    } else {
+                        self.0
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_const_queries! {
+    impl<T> for IOption<*const T, DefaultPolicy>: |value| value.is_null();
+    impl<T> for IOption<*mut T, DefaultPolicy>: |value| value.is_null();
+    #[cfg(feature = "nullable-core-floats")]
+    impl<> for IOption<f32, DefaultPolicy>: |value| nan::is_null_f32(value);
+    #[cfg(feature = "nullable-core-floats")]
+    impl<> for IOption<f64, DefaultPolicy>: |value| nan::is_null_f64(value);
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<i8, DefaultPolicy>: |value| value == i8::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<i16, DefaultPolicy>: |value| value == i16::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<i32, DefaultPolicy>: |value| value == i32::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<i64, DefaultPolicy>: |value| value == i64::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<i128, DefaultPolicy>: |value| value == i128::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<isize, DefaultPolicy>: |value| value == isize::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<u8, DefaultPolicy>: |value| value == u8::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<u16, DefaultPolicy>: |value| value == u16::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<u32, DefaultPolicy>: |value| value == u32::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<u64, DefaultPolicy>: |value| value == u64::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<u128, DefaultPolicy>: |value| value == u128::MAX;
+    #[cfg(feature = "nullable-core-ints")]
+    impl<> for IOption<usize, DefaultPolicy>: |value| value == usize::MAX;
+    impl<> for IOption<f32, policy::Nan>: |value| nan::is_null_f32(value);
+    impl<> for IOption<f64, policy::Nan>: |value| nan::is_null_f64(value);
+}
+
+/// [`impl_const_queries!`] for the [`Max`](policy::Max), [`Min`](policy::Min) and [`Zero`](policy::Zero) policies.
+macro_rules! impl_const_policy_queries {
+    (bounds: $($ty:ty),*; zero: $($zero_ty:ty),*) => {
+        impl_const_queries! {
+            $(
+                impl<> for IOption<$ty, policy::Max>: |value| value == <$ty>::MAX;
+                impl<> for IOption<$ty, policy::Min>: |value| value == <$ty>::MIN;
+            )*
+            $(
+                impl<> for IOption<$zero_ty, policy::Zero>: |value| value == 0;
+            )*
+        }
+    };
+}
+
+impl_const_policy_queries! {
+    bounds: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64;
+    zero: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+}
+
+impl<T, P: NullPolicy<T>> IOption<IOption<T, P>> {
+    /// Removes one level of nesting. Since an inner `None` is stored as an outer `None`, this is free.
+    ///
+    /// ```rust
+    /// use inline_option::{IOption, policy::Max};
+    ///
+    /// let nested = IOption::new(IOption::<u32, Max>::new_with_policy(7));
+    /// assert_eq!(nested.flatten(), Some(7));
+    ///
+    /// let nested = IOption::<IOption<u32, Max>>::none();
+    /// assert!(nested.flatten().is_none());
+    /// ```
+    #[inline]
+    pub fn flatten(self) -> IOption<T, P> {
+        self.0
+    }
+}
+
+impl<T, U, P> IOption<(T, U), P>
+where
+    P: NullPolicy<(T, U)> + NullPolicy<T> + NullPolicy<U>,
+{
+    #[inline]
+    pub fn unzip(self) -> (IOption<T, P>, IOption<U, P>) {
         if self.is_none() {
-            *self = IOption::new(T::default());
+            (IOption::none_with_policy(), IOption::none_with_policy())
+        } else {
+            let (a, b) = self.into_inner();
+            (IOption::new_with_policy(a), IOption::new_with_policy(b))
+        }
+    }
+}
+
+/// The error returned when a value passed as a `Some` is the null value of its [`IOption`].
+///
+/// It gives back the rejected value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SentinelCollision<T>(pub T);
+
+impl<T> SentinelCollision<T> {
+    /// Returns the rejected value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    #[inline]
+    fn check<P: NullPolicy<T>>(value: T) -> Result<T, Self> {
+        if P::is_null(&value) {
+            Err(SentinelCollision(value))
+        } else {
+            Ok(value)
         }
-        self.as_mut().unwrap()
     }
 }
 
-impl<T: Nullable> From<Option<T>> for IOption<T> {
+impl<T> fmt::Display for SentinelCollision<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value collides with the null value of `IOption`")
+    }
+}
+
+impl<T: fmt::Debug> core::error::Error for SentinelCollision<T> {}
+
+/// The error returned when an [`IOption`] that is `None` is converted into a [`Result`].
+///
+/// A `TryFrom<IOption<T>> for T` impl is not allowed by the orphan rules, so use [`IOption::into_result`] or `Result::from` instead:
+///
+/// ```rust
+/// use inline_option::{IOption, NoneError};
+/// # use inline_option::Nullable;
+/// # #[derive(Clone, Copy)]
+/// # struct Id(u32);
+/// # impl Nullable for Id {
+/// #     const NULL: Self = Id(u32::MAX);
+/// #     fn is_null(&self) -> bool { self.0 == u32::MAX }
+/// # }
+///
+/// fn lookup(ids: &[IOption<Id>], index: usize) -> Result<u32, NoneError> {
+///     let id = ids[index].into_result()?;
+///     Ok(id.0)
+/// }
+///
+/// let ids = [IOption::new(Id(7)), IOption::none()];
+/// assert_eq!(lookup(&ids, 0), Ok(7));
+/// assert_eq!(lookup(&ids, 1), Err(NoneError));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct NoneError;
+
+impl fmt::Display for NoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is `None`")
+    }
+}
+
+impl core::error::Error for NoneError {}
+
+impl<T, P: NullPolicy<T>> From<IOption<T, P>> for Result<T, NoneError> {
+    #[inline]
+    fn from(ioption: IOption<T, P>) -> Self {
+        ioption.into_result()
+    }
+}
+
+impl<T, P: NullPolicy<T>> From<Option<T>> for IOption<T, P> {
     #[inline]
+    #[track_caller]
     fn from(option: Option<T>) -> Self {
         match option {
-            Some(value) => IOption::new(value),
-            None => IOption::new(T::NULL),
+            Some(value) => {
+                Self::debug_assert_not_null(&value);
+                IOption::new_with_policy(value)
+            }
+            None => IOption::none_with_policy(),
         }
     }
 }
 
-impl<T: Nullable> From<IOption<T>> for Option<T> {
+impl<T, P: NullPolicy<T>> From<IOption<T, P>> for Option<T> {
     #[inline]
-    fn from(ioption: IOption<T>) -> Self {
+    fn from(ioption: IOption<T, P>) -> Self {
         if ioption.is_none() {
             None
         } else {
@@ -462,13 +1362,129 @@ impl<T: Nullable> From<IOption<T>> for Option<T> {
 
 impl<T: Nullable> From<T> for IOption<T> {
     #[inline]
+    #[track_caller]
     fn from(value: T) -> Self {
+        Self::debug_assert_not_null(&value);
         IOption::new(value)
     }
 }
 
+impl<T, P: NullPolicy<T>> IntoIterator for IOption<T, P> {
+    type Item = T;
+    type IntoIter = core::option::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Option::from(self).into_iter()
+    }
+}
+
+impl<'a, T, P: NullPolicy<T>> IntoIterator for &'a IOption<T, P> {
+    type Item = &'a T;
+    type IntoIter = core::option::IntoIter<&'a T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, P: NullPolicy<T>> IntoIterator for &'a mut IOption<T, P> {
+    type Item = &'a mut T;
+    type IntoIter = core::option::IntoIter<&'a mut T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Sums the elements of an iterator, returning `None` as soon as an element is `None`, like [`Option`] does.
+///
+/// A sum equal to the null value is also `None`, like [`IOption::new`] (with the `strict` feature, this panics in debug
+/// builds instead). For example, `[200u8, 55]` sums to `None` under [`Max`](policy::Max).
+impl<T, U, P> Sum<IOption<U, P>> for IOption<T, P>
+where
+    T: Sum<U>,
+    P: NullPolicy<T> + NullPolicy<U>,
+{
+    #[inline]
+    fn sum<I: Iterator<Item = IOption<U, P>>>(iter: I) -> Self {
+        iter.map(Option::from).sum::<Option<T>>().into()
+    }
+}
+
+/// Multiplies the elements of an iterator, returning `None` as soon as an element is `None`, like [`Option`] does.
+///
+/// A product equal to the null value is also `None`, like [`IOption::new`] (with the `strict` feature, this panics in debug
+/// builds instead). For example, `[15u8, 17]` multiplies to `None` under [`Max`](policy::Max).
+impl<T, U, P> Product<IOption<U, P>> for IOption<T, P>
+where
+    T: Product<U>,
+    P: NullPolicy<T> + NullPolicy<U>,
+{
+    #[inline]
+    fn product<I: Iterator<Item = IOption<U, P>>>(iter: I) -> Self {
+        iter.map(Option::from).product::<Option<T>>().into()
+    }
+}
+
+/// Allows `?` on an `IOption` in functions returning an `IOption` or an [`Option`], and `?` on an `Option` in functions returning an `IOption`.
+///
+/// The residual is the same as [`Option`]'s, so the two types can be mixed freely.
+#[cfg(feature = "nightly")]
+impl<T, P: NullPolicy<T>> core::ops::Try for IOption<T, P> {
+    type Output = T;
+    type Residual = Option<core::convert::Infallible>;
+
+    #[inline]
+    fn from_output(output: T) -> Self {
+        IOption::new_with_policy(output)
+    }
+
+    #[inline]
+    fn branch(self) -> core::ops::ControlFlow<Self::Residual, T> {
+        if self.is_none() {
+            core::ops::ControlFlow::Break(None)
+        } else {
+            core::ops::ControlFlow::Continue(self.into_inner())
+        }
+    }
+}
+
+#[cfg(feature = "nightly")]
+impl<T, P: NullPolicy<T>> core::ops::FromResidual<Option<core::convert::Infallible>>
+    for IOption<T, P>
+{
+    #[inline]
+    fn from_residual(_: Option<core::convert::Infallible>) -> Self {
+        IOption::none_with_policy()
+    }
+}
+
+/// Collects the elements of an iterator, returning `None` as soon as an element is `None`, like [`Option`] does.
+impl<A, V, P> FromIterator<IOption<A, P>> for IOption<V, P>
+where
+    V: FromIterator<A>,
+    P: NullPolicy<V> + NullPolicy<A>,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = IOption<A, P>>>(iter: I) -> Self {
+        iter.into_iter()
+            .map(Option::from)
+            .collect::<Option<V>>()
+            .into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{
+        format,
+        string::{String, ToString},
+        vec::Vec,
+    };
+
     use super::*;
 
     #[cfg(not(feature = "nullable-core-ints"))]
@@ -480,6 +1496,66 @@ mod tests {
         }
     }
 
+    impl Nullable for &'static i32 {
+        const NULL: Self = &i32::MAX;
+
+        fn is_null(&self) -> bool {
+            **self == i32::MAX
+        }
+    }
+
+    struct Negative;
+
+    impl NullPolicy<i32> for Negative {
+        const NULL: i32 = -1;
+
+        fn is_null(value: &i32) -> bool {
+            *value < 0
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Total(i64);
+
+    impl Nullable for Total {
+        const NULL: Self = Total(i64::MIN);
+
+        fn is_null(&self) -> bool {
+            self.0 == i64::MIN
+        }
+    }
+
+    impl FromIterator<i32> for Total {
+        fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+            Total(iter.into_iter().map(i64::from).sum())
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    struct Pair([i32; 2]);
+
+    impl Nullable for Pair {
+        const NULL: Self = Pair([i32::MAX; 2]);
+
+        fn is_null(&self) -> bool {
+            self.0[0] == i32::MAX
+        }
+    }
+
+    impl Deref for Pair {
+        type Target = [i32];
+
+        fn deref(&self) -> &[i32] {
+            &self.0
+        }
+    }
+
+    impl DerefMut for Pair {
+        fn deref_mut(&mut self) -> &mut [i32] {
+            &mut self.0
+        }
+    }
+
     #[test]
     fn test_nullable() {
         assert_eq!(i32::NULL, i32::MAX);
@@ -487,6 +1563,19 @@ mod tests {
         assert!(!42.is_null());
     }
 
+    #[test]
+    #[cfg(feature = "nullable-core-floats")]
+    fn test_nullable_floats() {
+        assert!(f32::NULL.is_null());
+        assert!(f64::NULL.is_null());
+        assert!(!f32::MAX.is_null());
+        assert!(!f64::MAX.is_null());
+        assert!(!f32::NAN.is_null());
+        assert!(!(-0.0f64).is_null());
+        assert!(IOption::new(f32::MAX).is_some());
+        assert!(IOption::<f64>::none().is_none());
+    }
+
     #[test]
     fn test_size() {
         assert_eq!(
@@ -519,6 +1608,26 @@ mod tests {
         assert!(!ioption.is_some());
     }
 
+    #[test]
+    fn test_is_some_and() {
+        let ioption = IOption::new(42);
+        assert!(ioption.is_some_and(|value| value > 40));
+        assert!(!ioption.is_some_and(|value| value > 50));
+
+        let ioption = IOption::new(i32::NULL);
+        assert!(!ioption.is_some_and(|_| true));
+    }
+
+    #[test]
+    fn test_is_none_or() {
+        let ioption = IOption::new(42);
+        assert!(ioption.is_none_or(|value| value > 40));
+        assert!(!ioption.is_none_or(|value| value > 50));
+
+        let ioption = IOption::new(i32::NULL);
+        assert!(ioption.is_none_or(|_| false));
+    }
+
     #[test]
     fn test_into_inner() {
         let ioption = IOption::new(42);
@@ -543,6 +1652,87 @@ mod tests {
         assert_eq!(ioption.as_mut(), None);
     }
 
+    #[test]
+    fn test_as_pin_ref() {
+        let ioption = IOption::new(42);
+        let pinned = Pin::new(&ioption);
+        assert_eq!(pinned.as_pin_ref().map(|value| *value), Some(42));
+
+        let ioption = IOption::new(i32::NULL);
+        let pinned = Pin::new(&ioption);
+        assert_eq!(pinned.as_pin_ref(), None);
+    }
+
+    #[test]
+    fn test_as_pin_mut() {
+        let mut ioption = IOption::new(42);
+        if let Some(mut value) = Pin::new(&mut ioption).as_pin_mut() {
+            *value = 84;
+        }
+        assert_eq!(ioption.into_inner(), 84);
+
+        let mut ioption = IOption::new(i32::NULL);
+        assert_eq!(Pin::new(&mut ioption).as_pin_mut(), None);
+    }
+
+    #[test]
+    fn test_as_slice() {
+        let ioption = IOption::new(42);
+        assert_eq!(ioption.as_slice(), &[42]);
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(ioption.as_slice(), &[] as &[i32]);
+    }
+
+    #[test]
+    fn test_as_mut_slice() {
+        let mut ioption = IOption::new(42);
+        ioption.as_mut_slice()[0] = 84;
+        assert_eq!(ioption.into_inner(), 84);
+
+        let mut ioption = IOption::new(i32::NULL);
+        assert!(ioption.as_mut_slice().is_empty());
+    }
+
+    #[test]
+    fn test_as_deref() {
+        let ioption = IOption::new(Pair([1, 2]));
+        assert_eq!(ioption.as_deref(), Some(&[1, 2][..]));
+
+        let ioption = IOption::new(Pair::NULL);
+        assert_eq!(ioption.as_deref(), None);
+    }
+
+    #[test]
+    fn test_as_deref_mut() {
+        let mut ioption = IOption::new(Pair([1, 2]));
+        if let Some(slice) = ioption.as_deref_mut() {
+            slice[1] = 3;
+        }
+        assert_eq!(ioption.into_inner(), Pair([1, 3]));
+
+        let mut ioption = IOption::new(Pair::NULL);
+        assert_eq!(ioption.as_deref_mut(), None);
+    }
+
+    #[test]
+    fn test_copied() {
+        let ioption = IOption::<&i32>::new(&42);
+        assert_eq!(ioption.copied().into_inner(), 42);
+
+        let ioption = IOption::<&i32>::none();
+        assert!(ioption.copied().is_none());
+    }
+
+    #[test]
+    fn test_cloned() {
+        let ioption = IOption::<&i32>::new(&42);
+        assert_eq!(ioption.cloned().into_inner(), 42);
+
+        let ioption = IOption::<&i32>::none();
+        assert!(ioption.cloned().is_none());
+    }
+
     #[test]
     fn test_map() {
         let ioption = IOption::new(42);
@@ -576,6 +1766,67 @@ mod tests {
         assert_eq!(value, 0);
     }
 
+    #[test]
+    fn test_map_or_non_nullable() {
+        let ioption = IOption::new(42);
+        let value = ioption.map_or(String::new(), |value| value.to_string());
+        assert_eq!(value, "42");
+
+        let ioption = IOption::new(i32::NULL);
+        let value = ioption.map_or_else(String::new, |value| value.to_string());
+        assert_eq!(value, "");
+    }
+
+    #[test]
+    fn test_map_option() {
+        let ioption = IOption::new(42);
+        let value = ioption.map_option(|value| value.to_string());
+        assert_eq!(value.as_deref(), Some("42"));
+
+        let ioption = IOption::new(i32::NULL);
+        let value = ioption.map_option(|value| value.to_string());
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_and_then_option() {
+        let ioption = IOption::new(42);
+        let value = ioption.and_then_option(|value| u8::try_from(value).ok());
+        assert_eq!(value, Some(42u8));
+
+        let ioption = IOption::new(-1);
+        let value = ioption.and_then_option(|value| u8::try_from(value).ok());
+        assert_eq!(value, None);
+
+        let ioption = IOption::new(i32::NULL);
+        let value = ioption.and_then_option(|value| Some(value.to_string()));
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_filter_map_option() {
+        let ioption = IOption::new(42);
+        let value = ioption.filter_map_option(|value| (value % 2 == 0).then(|| value.to_string()));
+        assert_eq!(value.as_deref(), Some("42"));
+
+        let ioption = IOption::new(43);
+        let value = ioption.filter_map_option(|value| (value % 2 == 0).then(|| value.to_string()));
+        assert_eq!(value, None);
+    }
+
+    #[test]
+    fn test_inspect() {
+        let mut seen = None;
+        let ioption = IOption::new(42).inspect(|value| seen = Some(*value));
+        assert_eq!(ioption.into_inner(), 42);
+        assert_eq!(seen, Some(42));
+
+        let mut seen = None;
+        let ioption = IOption::new(i32::NULL).inspect(|value| seen = Some(*value));
+        assert!(ioption.is_none());
+        assert_eq!(seen, None);
+    }
+
     #[test]
     fn test_ok_or() {
         let ioption = IOption::new(42);
@@ -598,6 +1849,40 @@ mod tests {
         assert_eq!(result, Err("error"));
     }
 
+    #[test]
+    fn test_into_result() {
+        fn double(ioption: IOption<i32>) -> Result<i32, NoneError> {
+            Ok(ioption.into_result()? * 2)
+        }
+
+        assert_eq!(double(IOption::new(42)), Ok(84));
+        assert_eq!(double(IOption::new(i32::NULL)), Err(NoneError));
+        assert_eq!(Result::from(IOption::new(42)), Ok(42));
+    }
+
+    #[test]
+    #[cfg(feature = "nightly")]
+    fn test_try() {
+        fn double(ioption: IOption<i32>) -> IOption<i32> {
+            IOption::new(ioption? * 2)
+        }
+
+        fn double_option(ioption: IOption<i32>) -> Option<i32> {
+            Some(ioption? * 2)
+        }
+
+        fn double_from_option(option: Option<i32>) -> IOption<i32> {
+            IOption::new(option? * 2)
+        }
+
+        assert_eq!(double(IOption::new(42)).into_inner(), 84);
+        assert!(double(IOption::new(i32::NULL)).is_none());
+        assert_eq!(double_option(IOption::new(42)), Some(84));
+        assert_eq!(double_option(IOption::new(i32::NULL)), None);
+        assert_eq!(double_from_option(Some(42)).into_inner(), 84);
+        assert!(double_from_option(None).is_none());
+    }
+
     #[test]
     fn test_and() {
         let ioption = IOption::new(42);
@@ -646,6 +1931,80 @@ mod tests {
         assert_eq!(result.into_inner(), 84);
     }
 
+    #[test]
+    fn test_xor() {
+        let ioption = IOption::new(42);
+        let other = IOption::new(84);
+        assert!(ioption.xor(other).is_none());
+
+        let ioption = IOption::new(42);
+        let other = IOption::new(i32::NULL);
+        assert_eq!(ioption.xor(other).into_inner(), 42);
+
+        let ioption = IOption::new(i32::NULL);
+        let other = IOption::new(84);
+        assert_eq!(ioption.xor(other).into_inner(), 84);
+
+        let ioption = IOption::new(i32::NULL);
+        let other = IOption::new(i32::NULL);
+        assert!(ioption.xor(other).is_none());
+    }
+
+    #[test]
+    fn test_zip() {
+        let ioption = IOption::new(42);
+        let other = IOption::new(84);
+        assert_eq!(ioption.zip(other).into_inner(), (42, 84));
+        assert_eq!(
+            core::mem::size_of::<IOption<(i32, i32)>>(),
+            core::mem::size_of::<(i32, i32)>()
+        );
+
+        let ioption = IOption::new(42);
+        let other = IOption::new(i32::NULL);
+        assert!(ioption.zip(other).is_none());
+    }
+
+    #[test]
+    fn test_zip_with() {
+        let ioption = IOption::new(42);
+        let other = IOption::new(84);
+        assert_eq!(ioption.zip_with(other, |a, b| a + b).into_inner(), 126);
+
+        let ioption = IOption::new(i32::NULL);
+        let other = IOption::new(84);
+        assert!(ioption.zip_with(other, |a, b| a + b).is_none());
+    }
+
+    #[test]
+    fn test_zip_option() {
+        let ioption = IOption::new(42);
+        let other = IOption::new(84);
+        assert_eq!(ioption.zip_option(other), Some((42, 84)));
+        assert_eq!(
+            ioption.zip_with_option(other, |a, b| [a, b]),
+            Some([42, 84])
+        );
+
+        let ioption = IOption::new(42);
+        let other = IOption::new(i32::NULL);
+        assert_eq!(ioption.zip_option(other), None);
+        assert_eq!(ioption.zip_with_option(other, |a, b| [a, b]), None);
+    }
+
+    #[test]
+    fn test_unzip() {
+        let ioption = IOption::new((42, 84));
+        let (a, b) = ioption.unzip();
+        assert_eq!(a.into_inner(), 42);
+        assert_eq!(b.into_inner(), 84);
+
+        let ioption = IOption::new((42, i32::NULL));
+        let (a, b) = ioption.unzip();
+        assert!(a.is_none());
+        assert!(b.is_none());
+    }
+
     #[test]
     fn test_unwrap() {
         let ioption = IOption::new(42);
@@ -690,6 +2049,36 @@ mod tests {
         ioption.expect("error");
     }
 
+    #[test]
+    fn test_debug() {
+        let ioption = IOption::new(42);
+        assert_eq!(format!("{ioption:?}"), "Some(42)");
+        assert_eq!(format!("{ioption:#?}"), format!("{:#?}", Some(42)));
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(format!("{ioption:?}"), "None");
+    }
+
+    #[test]
+    fn test_display() {
+        let ioption = IOption::new(42);
+        assert_eq!(format!("{ioption}"), "42");
+        assert_eq!(format!("{ioption:>4}"), "  42");
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(format!("{ioption}"), "None");
+    }
+
+    #[test]
+    fn test_display_or() {
+        let ioption = IOption::new(42);
+        assert_eq!(ioption.display_or("-").to_string(), "42");
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(ioption.display_or("-").to_string(), "-");
+        assert_eq!(format!("{:>3}", ioption.display_or("-")), "  -");
+    }
+
     #[test]
     fn test_iter() {
         let ioption = IOption::new(42);
@@ -706,6 +2095,85 @@ mod tests {
         assert_eq!(iter.next(), None);
     }
 
+    #[test]
+    fn test_into_iter() {
+        let ioption = IOption::new(42);
+        let mut iter = ioption.into_iter();
+        assert_eq!(iter.next(), Some(42));
+        assert_eq!(iter.next(), None);
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(ioption.into_iter().next(), None);
+    }
+
+    #[test]
+    fn test_into_iter_ref() {
+        let mut ioption = IOption::new(42);
+        for value in &mut ioption {
+            *value += 1;
+        }
+        let mut seen = None;
+        for value in &ioption {
+            seen = Some(*value);
+        }
+        assert_eq!(seen, Some(43));
+    }
+
+    #[test]
+    fn test_flatten() {
+        let ioptions = [IOption::new(1), IOption::new(i32::NULL), IOption::new(3)];
+        let values: Vec<i32> = ioptions.into_iter().flatten().collect();
+        assert_eq!(values, [1, 3]);
+    }
+
+    #[test]
+    fn test_sum() {
+        let ioptions = [IOption::new(1), IOption::new(2), IOption::new(3)];
+        let sum: IOption<i32> = ioptions.into_iter().sum();
+        assert_eq!(sum.into_inner(), 6);
+
+        let ioptions = [IOption::new(1), IOption::new(i32::NULL), IOption::new(3)];
+        let sum: IOption<i32> = ioptions.into_iter().sum();
+        assert!(sum.is_none());
+    }
+
+    #[test]
+    fn test_product() {
+        let ioptions = [IOption::new(2), IOption::new(3), IOption::new(4)];
+        let product: IOption<i32> = ioptions.into_iter().product();
+        assert_eq!(product.into_inner(), 24);
+
+        let ioptions = [IOption::new(2), IOption::new(i32::NULL)];
+        let product: IOption<i32> = ioptions.into_iter().product();
+        assert!(product.is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_sum_product_null_result() {
+        let ioptions = [
+            IOption::new_with_policy(200u8),
+            IOption::new_with_policy(55),
+        ];
+        let sum: IOption<u8, policy::Max> = ioptions.into_iter().sum();
+        assert!(sum.is_none());
+
+        let ioptions = [IOption::new_with_policy(15u8), IOption::new_with_policy(17)];
+        let product: IOption<u8, policy::Max> = ioptions.into_iter().product();
+        assert!(product.is_none());
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let ioptions = [IOption::new(1), IOption::new(2), IOption::new(3)];
+        let total: IOption<Total> = ioptions.into_iter().collect();
+        assert_eq!(total.into_inner(), Total(6));
+
+        let ioptions = [IOption::new(1), IOption::new(i32::NULL), IOption::new(3)];
+        let total: IOption<Total> = ioptions.into_iter().collect();
+        assert!(total.is_none());
+    }
+
     #[test]
     fn test_filter() {
         let ioption = IOption::new(42);
@@ -725,6 +2193,38 @@ mod tests {
         assert!(ioption.is_none());
     }
 
+    #[test]
+    fn test_take_if() {
+        let mut ioption = IOption::new(42);
+        let result = ioption.take_if(|value| *value > 50);
+        assert!(result.is_none());
+        assert_eq!(ioption.into_inner(), 42);
+
+        let result = ioption.take_if(|value| {
+            *value += 1;
+            *value > 40
+        });
+        assert_eq!(result.into_inner(), 43);
+        assert!(ioption.is_none());
+
+        let mut ioption = IOption::new(i32::NULL);
+        let result = ioption.take_if(|_| true);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut ioption = IOption::new(i32::NULL);
+        let value = ioption.insert(42);
+        assert_eq!(value, &42);
+        *value = 84;
+        assert_eq!(ioption.into_inner(), 84);
+
+        let mut ioption = IOption::new(42);
+        ioption.insert(84);
+        assert_eq!(ioption.into_inner(), 84);
+    }
+
     #[test]
     fn test_replace() {
         let mut ioption = IOption::new(42);
@@ -760,6 +2260,8 @@ mod tests {
     }
 
     #[test]
+    // rkyv's `rend` dependency adds `PartialEq` impls to the integer types, so `Default::default()` cannot be inferred here.
+    #[cfg(not(feature = "rkyv"))]
     fn test_get_or_insert_default() {
         let mut ioption = IOption::new(42);
         let value = ioption.get_or_insert_default();
@@ -772,6 +2274,74 @@ mod tests {
         assert_eq!(ioption.into_inner(), Default::default());
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_get_or_insert_null() {
+        let mut ioption = IOption::<i32>::none();
+        let value = ioption.get_or_insert_with(|| i32::NULL);
+        assert_eq!(value, &i32::NULL);
+        assert!(ioption.is_none());
+    }
+
+    #[test]
+    fn test_try_some() {
+        let result = IOption::try_some(42);
+        assert_eq!(result.map(IOption::into_inner), Ok(42));
+
+        let result = IOption::try_some(i32::NULL);
+        assert_eq!(result, Err(SentinelCollision(i32::NULL)));
+    }
+
+    #[test]
+    fn test_try_replace() {
+        let mut ioption = IOption::new(42);
+        let result = ioption.try_replace(84);
+        assert_eq!(result.map(IOption::into_inner), Ok(42));
+        assert_eq!(ioption.into_inner(), 84);
+
+        let result = ioption.try_replace(i32::NULL);
+        assert_eq!(result, Err(SentinelCollision(i32::NULL)));
+        assert_eq!(ioption.into_inner(), 84);
+    }
+
+    #[test]
+    fn test_try_get_or_insert() {
+        let mut ioption = IOption::new(42);
+        let value = ioption.try_get_or_insert(i32::NULL);
+        assert_eq!(value, Ok(&mut 42));
+
+        let mut ioption = IOption::<i32>::none();
+        let value = ioption.try_get_or_insert(i32::NULL);
+        assert_eq!(value, Err(SentinelCollision(i32::NULL)));
+        assert!(ioption.is_none());
+
+        let value = ioption.try_get_or_insert_with(|| 84);
+        assert_eq!(value, Ok(&mut 84));
+        assert_eq!(ioption.into_inner(), 84);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict", debug_assertions))]
+    #[should_panic]
+    fn test_strict_replace() {
+        let mut ioption = IOption::new(42);
+        ioption.replace(i32::NULL);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict", debug_assertions))]
+    #[should_panic]
+    fn test_strict_from_value() {
+        let _ = IOption::from(i32::NULL);
+    }
+
+    #[test]
+    #[cfg(all(feature = "strict", debug_assertions))]
+    #[should_panic]
+    fn test_strict_from_option() {
+        let _ = IOption::<i32>::from(Some(i32::NULL));
+    }
+
     #[test]
     fn test_from_option() {
         let option = Some(42);
@@ -801,9 +2371,333 @@ mod tests {
         assert_eq!(ioption.into_inner(), 42);
     }
 
+    #[test]
+    fn test_ord() {
+        let none = IOption::new(i32::NULL);
+        let small = IOption::new(-1);
+        let large = IOption::new(42);
+        assert!(none < small);
+        assert!(none < large);
+        assert!(small < large);
+        assert_eq!(none.cmp(&none), Ordering::Equal);
+
+        let mut values = [large, none, small];
+        values.sort();
+        assert_eq!(values, [none, small, large]);
+    }
+
+    #[test]
+    fn test_eq_option() {
+        let ioption = IOption::new(42);
+        assert_eq!(ioption, Some(42));
+        assert_eq!(Some(42), ioption);
+        assert_ne!(ioption, None);
+
+        let ioption = IOption::new(i32::NULL);
+        assert_eq!(ioption, None);
+        assert_eq!(None, ioption);
+        assert_ne!(ioption, Some(i32::NULL));
+    }
+
+    #[test]
+    fn test_eq_value() {
+        let ioption = IOption::new(42);
+        assert_eq!(ioption, 42);
+        assert_ne!(ioption, 84);
+
+        let ioption = IOption::new(i32::NULL);
+        assert_ne!(ioption, i32::NULL);
+    }
+
+    #[test]
+    fn test_ord_option() {
+        let none = IOption::new(i32::NULL);
+        let some = IOption::new(42);
+        assert!(none < Some(-1));
+        assert!(some > None);
+        assert!(some < Some(84));
+        assert!(None < some);
+        assert!(Some(84) > some);
+        assert_eq!(none.partial_cmp(&None), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_ord_value() {
+        let none = IOption::new(i32::NULL);
+        let some = IOption::new(42);
+        assert!(none < i32::MIN);
+        assert!(some > 41);
+        assert!(some < 43);
+        assert_eq!(some.partial_cmp(&42), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_raw_ord() {
+        let none = RawOrd(IOption::new(i32::NULL));
+        let large = RawOrd(IOption::new(42));
+        assert!(large < none);
+        assert_eq!(none.cmp(&none), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_raw_ord_custom_policy() {
+        let mut values = [
+            IOption::<i32, Negative>::new_with_policy(3),
+            IOption::none_with_policy(),
+            IOption::new_with_policy(1),
+        ];
+        values.sort_by_key(|value| RawOrd(*value));
+        assert_eq!(values, [None, Some(1), Some(3)]);
+        assert_eq!(
+            format!("{:?}", RawOrd::<i32, Negative>::default()),
+            "RawOrd(None)"
+        );
+    }
+
+    #[test]
+    fn test_eq_canonical_null() {
+        let a = IOption::<i32, Negative>::new_with_policy(-1);
+        let b = IOption::<i32, Negative>::new_with_policy(-5);
+        assert_eq!(a, b);
+        assert_ne!(a, IOption::new_with_policy(5));
+        assert_eq!(
+            IOption::<i32, Negative>::new_with_policy(5),
+            IOption::new_with_policy(5)
+        );
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(IOption::new(42)), state.hash_one(Some(42)));
+        assert_eq!(
+            state.hash_one(IOption::new(i32::NULL)),
+            state.hash_one(None::<i32>)
+        );
+
+        let a = IOption::<i32, Negative>::new_with_policy(-1);
+        let b = IOption::<i32, Negative>::new_with_policy(-5);
+        assert_eq!(state.hash_one(a), state.hash_one(b));
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(a, "none");
+        assert_eq!(map.get(&b), Some(&"none"));
+    }
+
     #[test]
     fn test_default() {
         let ioption = IOption::<i32>::default();
         assert!(ioption.is_none());
     }
+
+    #[test]
+    fn test_nested() {
+        assert_eq!(
+            core::mem::size_of::<IOption<IOption<i32>>>(),
+            core::mem::size_of::<i32>()
+        );
+
+        let nested = IOption::new(IOption::new(42));
+        assert!(nested.is_some());
+        assert_eq!(nested.flatten(), Some(42));
+
+        let nested = IOption::new(IOption::<i32>::none());
+        assert!(nested.is_none());
+        assert!(nested.flatten().is_none());
+
+        let halved = IOption::new(42).map(|value| {
+            if value % 2 == 0 {
+                IOption::new(value / 2)
+            } else {
+                IOption::none()
+            }
+        });
+        assert_eq!(halved.flatten(), Some(21));
+    }
+
+    #[test]
+    fn test_none_const() {
+        static TABLE: [IOption<i32>; 4] = [IOption::NONE; 4];
+        assert!(TABLE.iter().all(IOption::is_none));
+
+        const INNER: i32 = IOption::<i32>::new(7).into_inner();
+        assert_eq!(INNER, 7);
+    }
+
+    #[test]
+    fn test_const_queries_ptr() {
+        static VALUE: u8 = 7;
+        const SOME: IOption<*const u8> = IOption::new(&VALUE);
+        const NONE: IOption<*const u8> = IOption::NONE;
+        const _: () = assert!(SOME.const_is_some() && NONE.const_is_none());
+        assert_eq!(NONE.const_unwrap_or(&VALUE), &VALUE as *const u8);
+    }
+
+    #[test]
+    #[cfg(feature = "nullable-core-ints")]
+    fn test_const_queries_int() {
+        const TABLE: [IOption<u8>; 4] = {
+            let mut table = [IOption::NONE; 4];
+            table[2] = IOption::new(42);
+            table
+        };
+        const FIRST: usize = {
+            let mut index = 0;
+            while TABLE[index].const_is_none() {
+                index += 1;
+            }
+            index
+        };
+        const VALUE: u8 = TABLE[FIRST].const_unwrap_or(0);
+        assert_eq!((FIRST, VALUE), (2, 42));
+        assert_eq!(TABLE[0].const_unwrap_or(1), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "nullable-core-floats")]
+    fn test_const_queries_float() {
+        const NONE: IOption<f64> = IOption::NONE;
+        const NAN: IOption<f64> = IOption::new(f64::NAN);
+        const _: () = assert!(NONE.const_is_none() && NAN.const_is_some());
+        assert_eq!(NONE.const_unwrap_or(1.5), 1.5);
+    }
+
+    #[test]
+    fn test_const_queries_policy() {
+        use crate::policy::{Max, Min, Nan, Zero};
+
+        const MAX: IOption<u32, Max> = IOption::NONE;
+        const MIN: IOption<i8, Min> = IOption::new_with_policy(0);
+        const ZERO: IOption<u64, Zero> = IOption::new_with_policy(0);
+        const NAN: IOption<f32, Nan> = IOption::new_with_policy(f32::NAN);
+        const _: () = assert!(MAX.const_is_none() && MIN.const_is_some());
+        const _: () = assert!(ZERO.const_is_none() && NAN.const_is_some());
+        assert_eq!(MAX.const_unwrap_or(3), 3);
+        assert_eq!(IOption::<f64, Max>::NONE.const_unwrap_or(0.5), 0.5);
+    }
+
+    #[test]
+    fn test_from_ref() {
+        let value = 42;
+        assert_eq!(IOption::<i32>::from_ref(&value).unwrap(), 42);
+        assert!(IOption::<i32>::from_ref(&i32::MAX).is_none());
+
+        let mut value = 42;
+        let ioption: &mut IOption<i32> = IOption::from_mut(&mut value);
+        assert_eq!(ioption.take().unwrap(), 42);
+        assert_eq!(value, i32::MAX);
+
+        let ioption: &mut IOption<i32, Negative> = IOption::from_mut(&mut value);
+        assert!(ioption.is_some());
+        *ioption = IOption::none_with_policy();
+        assert_eq!(value, -1);
+    }
+
+    #[test]
+    fn test_slice_casts() {
+        let values = [1, i32::MAX, 3];
+        let ioptions: &[IOption<i32>] = IOption::slice_from_inner(&values);
+        assert_eq!(ioptions, [Some(1), None, Some(3)]);
+        assert_eq!(IOption::slice_as_inner(ioptions), values);
+        assert_eq!(IOption::<i32>::slice_from_inner(&[]).len(), 0);
+
+        let mut values = [1, 2, 3];
+        let ioptions: &mut [IOption<i32>] = IOption::slice_from_inner_mut(&mut values);
+        ioptions[0].take();
+        *ioptions[2].as_mut().unwrap() *= 10;
+        let raw = IOption::slice_as_inner_mut(ioptions);
+        raw[1] = i32::MAX;
+        assert_eq!(values, [i32::MAX, i32::MAX, 30]);
+
+        let mut ioptions = [IOption::<i32>::new(5), IOption::none()];
+        IOption::slice_as_inner_mut(&mut ioptions)[1] = 6;
+        assert_eq!(ioptions, [Some(5), Some(6)]);
+    }
+
+    mod conformance {
+        mod ioption {
+            use crate::IOption;
+
+            crate::nullable_conformance_tests!(
+                IOption<i32>,
+                samples = [IOption::new(0), IOption::new(i32::MIN)]
+            );
+        }
+
+        mod option {
+            crate::nullable_conformance_tests!(Option<u8>, samples = [Some(0), Some(u8::MAX)]);
+        }
+
+        mod const_ptr {
+            crate::nullable_conformance_tests!(
+                *const u8,
+                samples = [core::ptr::NonNull::<u8>::dangling().as_ptr().cast_const()]
+            );
+        }
+
+        mod mut_ptr {
+            crate::nullable_conformance_tests!(
+                *mut u8,
+                samples = [core::ptr::NonNull::<u8>::dangling().as_ptr()]
+            );
+        }
+
+        mod pair {
+            crate::nullable_conformance_tests!(
+                (Option<u8>, Option<u16>),
+                samples = [(Some(0), Some(0)), (Some(u8::MAX), Some(u16::MAX))]
+            );
+        }
+
+        #[cfg(feature = "nullable-core-floats")]
+        mod float_f32 {
+            crate::nullable_conformance_tests!(
+                f32,
+                samples = [
+                    0.0,
+                    -0.0,
+                    f32::MAX,
+                    f32::MIN,
+                    f32::INFINITY,
+                    f32::NEG_INFINITY
+                ]
+            );
+        }
+
+        #[cfg(feature = "nullable-core-floats")]
+        mod float_f64 {
+            crate::nullable_conformance_tests!(
+                f64,
+                samples = [
+                    0.0,
+                    -0.0,
+                    f64::MAX,
+                    f64::MIN,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY
+                ]
+            );
+        }
+
+        macro_rules! int_conformance {
+            ($($name:ident: $ty:ident),*) => {
+                $(
+                    #[cfg(feature = "nullable-core-ints")]
+                    mod $name {
+                        crate::nullable_conformance_tests!(
+                            $ty,
+                            samples = [0, 1, $ty::MIN, $ty::MAX - 1]
+                        );
+                    }
+                )*
+            };
+        }
+
+        int_conformance!(
+            int_i8: i8, int_i16: i16, int_i32: i32, int_i64: i64, int_i128: i128, int_isize: isize,
+            int_u8: u8, int_u16: u16, int_u32: u32, int_u64: u64, int_u128: u128, int_usize: usize
+        );
+    }
 }
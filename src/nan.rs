@@ -0,0 +1,97 @@
+//! NaN-based null values for `f32` and `f64`.
+//!
+//! The null value of a float is a single reserved quiet NaN bit pattern, [`F32_NULL_BITS`] or [`F64_NULL_BITS`].
+//! A value is null if and only if its bits, as returned by `to_bits`, are exactly that pattern. This means:
+//!
+//! - Every non-NaN value is a valid, non-null value, including `MAX`, `MIN`, the infinities, `0.0` and `-0.0`.
+//! - [`f32::NAN`], [`f64::NAN`] and NaNs produced by arithmetic such as `0.0 / 0.0` are *not* null, since they never carry the reserved payload.
+//!   They are ordinary values, just like in an `Option<f32>`.
+//! - Arithmetic on a null value generally propagates its payload, so the result is usually null again.
+//!   This is platform dependent and should not be relied upon.
+//!
+//! Copies, `to_bits` and `from_bits` preserve NaN payloads, so a null value stays null as long as it is only moved around.
+
+/// The bit pattern of the null `f32`: a quiet NaN with a payload of `1`.
+pub const F32_NULL_BITS: u32 = 0x7fc0_0001;
+
+/// The bit pattern of the null `f64`: a quiet NaN with a payload of `1`.
+pub const F64_NULL_BITS: u64 = 0x7ff8_0000_0000_0001;
+
+/// The null `f32`.
+pub const F32_NULL: f32 = f32::from_bits(F32_NULL_BITS);
+
+/// The null `f64`.
+pub const F64_NULL: f64 = f64::from_bits(F64_NULL_BITS);
+
+/// Returns `true` if the bits of `value` are exactly [`F32_NULL_BITS`].
+#[inline]
+pub const fn is_null_f32(value: f32) -> bool {
+    value.to_bits() == F32_NULL_BITS
+}
+
+/// Returns `true` if the bits of `value` are exactly [`F64_NULL_BITS`].
+#[inline]
+pub const fn is_null_f64(value: f64) -> bool {
+    value.to_bits() == F64_NULL_BITS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_is_nan() {
+        assert!(F32_NULL.is_nan());
+        assert!(F64_NULL.is_nan());
+        assert!(is_null_f32(F32_NULL));
+        assert!(is_null_f64(F64_NULL));
+    }
+
+    #[test]
+    fn test_ordinary_values() {
+        for value in [
+            0.0,
+            -0.0,
+            1.0,
+            f32::MAX,
+            f32::MIN,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+        ] {
+            assert!(!is_null_f32(value));
+        }
+        for value in [
+            0.0,
+            -0.0,
+            1.0,
+            f64::MAX,
+            f64::MIN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+        ] {
+            assert!(!is_null_f64(value));
+        }
+    }
+
+    #[test]
+    fn test_ordinary_nans() {
+        let zero = core::hint::black_box(0.0f32);
+        assert!(!is_null_f32(f32::NAN));
+        assert!(!is_null_f32(zero / zero));
+        assert!(!is_null_f32(f32::INFINITY - f32::INFINITY));
+
+        let zero = core::hint::black_box(0.0f64);
+        assert!(!is_null_f64(f64::NAN));
+        assert!(!is_null_f64(zero / zero));
+        assert!(!is_null_f64(f64::INFINITY - f64::INFINITY));
+    }
+
+    #[test]
+    fn test_copy_preserves_null() {
+        let values = [F32_NULL; 4];
+        assert!(values.iter().all(|value| is_null_f32(*value)));
+
+        let values = [F64_NULL; 4];
+        assert!(values.iter().all(|value| is_null_f64(*value)));
+    }
+}
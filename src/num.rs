@@ -0,0 +1,447 @@
+//! Integer newtypes that can never hold their type's null value.
+//!
+//! `IOption::new(u32::MAX)` silently creates a `None` when `u32` uses `MAX` as null. The types in this module reserve that value instead:
+//! their constructors reject it, so a value of type [`NonMaxU32`] always ends up as a `Some` when stored in an [`IOption`][crate::IOption].
+//!
+//! - `NonMaxU8` through `NonMaxU128`, `NonMaxUsize`, `NonMaxI8` through `NonMaxI128` and `NonMaxIsize` reserve `MAX`.
+//! - `NonMinI8` through `NonMinI128` and `NonMinIsize` reserve `MIN`.
+//!
+//! Every type implements [`Nullable`] with its reserved value as null, and has the same size as its underlying integer.
+//! Arithmetic operators panic if the result overflows or is the reserved value. Use the `checked_*` methods to handle those cases instead.
+//!
+//! ```rust
+//! use inline_option::{IOption, num::NonMaxU32};
+//!
+//! assert!(NonMaxU32::new(u32::MAX).is_none());
+//!
+//! let value = NonMaxU32::new(41).unwrap() + NonMaxU32::new(1).unwrap();
+//! let opt = IOption::new(value);
+//! assert_eq!(opt.unwrap().get(), 42);
+//! assert_eq!(size_of::<IOption<NonMaxU32>>(), size_of::<u32>());
+//! ```
+
+use core::{
+    fmt,
+    num::ParseIntError,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::Nullable;
+
+/// The error returned when converting the reserved value into one of the newtypes in this module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReservedValueError;
+
+impl fmt::Display for ReservedValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is reserved as the null value")
+    }
+}
+
+impl core::error::Error for ReservedValueError {}
+
+/// The error returned when parsing one of the newtypes in this module from a string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The string is not a valid integer.
+    Int(ParseIntError),
+    /// The string is the reserved value.
+    Reserved(ReservedValueError),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Int(err) => err.fmt(f),
+            ParseError::Reserved(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            ParseError::Int(err) => Some(err),
+            ParseError::Reserved(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParseIntError> for ParseError {
+    #[inline]
+    fn from(err: ParseIntError) -> Self {
+        ParseError::Int(err)
+    }
+}
+
+impl From<ReservedValueError> for ParseError {
+    #[inline]
+    fn from(err: ReservedValueError) -> Self {
+        ParseError::Reserved(err)
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($name:ident, $op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $checked:ident, $what:literal) => {
+        impl $name {
+            #[doc = concat!("Checked ", $what, ". Returns `None` on overflow, division by zero, or if the result is the reserved value.")]
+            #[inline]
+            pub const fn $checked(self, rhs: Self) -> Option<Self> {
+                match self.0.$checked(rhs.0) {
+                    Some(value) => Self::new(value),
+                    None => None,
+                }
+            }
+        }
+
+        impl $op for $name {
+            type Output = Self;
+
+            #[inline]
+            #[track_caller]
+            fn $op_fn(self, rhs: Self) -> Self {
+                match self.$checked(rhs) {
+                    Some(value) => value,
+                    None => panic!(concat!(
+                        "attempt to compute `",
+                        stringify!($name),
+                        "` ",
+                        $what,
+                        " with overflow or a reserved result"
+                    )),
+                }
+            }
+        }
+
+        impl $assign for $name {
+            #[inline]
+            #[track_caller]
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = $op::$op_fn(*self, rhs);
+            }
+        }
+    };
+}
+
+macro_rules! nonmax {
+    ($($name:ident($int:ty, $reserved:ident, $range:literal);)*) => {
+        $(
+            #[doc = concat!("A `", stringify!($int), "` that is never `", stringify!($int), "::", stringify!($reserved), "`, which is used as its null value. Valid values are ", $range, ".")]
+            #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #[repr(transparent)]
+            pub struct $name($int);
+
+            impl $name {
+                /// The smallest valid value.
+                pub const MIN: Self = Self(if <$int>::$reserved == <$int>::MIN { <$int>::MIN + 1 } else { <$int>::MIN });
+
+                /// The largest valid value.
+                pub const MAX: Self = Self(if <$int>::$reserved == <$int>::MAX { <$int>::MAX - 1 } else { <$int>::MAX });
+
+                /// Creates a new value, or returns `None` if `value` is the reserved value.
+                #[inline]
+                pub const fn new(value: $int) -> Option<Self> {
+                    if value == <$int>::$reserved {
+                        None
+                    } else {
+                        Some(Self(value))
+                    }
+                }
+
+                /// Creates a new value without checking it.
+                ///
+                /// # Safety
+                ///
+                #[doc = concat!("`value` must not be `", stringify!($int), "::", stringify!($reserved), "`.")]
+                #[inline]
+                pub const unsafe fn new_unchecked(value: $int) -> Self {
+                    debug_assert!(value != <$int>::$reserved);
+                    Self(value)
+                }
+
+                /// Returns the value as a primitive integer.
+                #[inline]
+                pub const fn get(self) -> $int {
+                    self.0
+                }
+            }
+
+            impl Nullable for $name {
+                const NULL: Self = Self(<$int>::$reserved);
+
+                #[inline]
+                fn is_null(&self) -> bool {
+                    self.0 == <$int>::$reserved
+                }
+            }
+
+            impl From<$name> for $int {
+                #[inline]
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl TryFrom<$int> for $name {
+                type Error = ReservedValueError;
+
+                #[inline]
+                fn try_from(value: $int) -> Result<Self, Self::Error> {
+                    Self::new(value).ok_or(ReservedValueError)
+                }
+            }
+
+            impl FromStr for $name {
+                type Err = ParseError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Ok(Self::try_from(s.parse::<$int>()?)?)
+                }
+            }
+
+            impl fmt::Debug for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Debug::fmt(&self.0, f)
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+
+            impl_binary_op!($name, Add, add, AddAssign, add_assign, checked_add, "addition");
+            impl_binary_op!($name, Sub, sub, SubAssign, sub_assign, checked_sub, "subtraction");
+            impl_binary_op!($name, Mul, mul, MulAssign, mul_assign, checked_mul, "multiplication");
+            impl_binary_op!($name, Div, div, DivAssign, div_assign, checked_div, "division");
+            impl_binary_op!($name, Rem, rem, RemAssign, rem_assign, checked_rem, "remainder");
+        )*
+    };
+}
+
+macro_rules! nonmin_neg {
+    ($($name:ident),*) => {
+        $(
+            impl Neg for $name {
+                type Output = Self;
+
+                /// Negation can never overflow or produce `MIN`, since `MIN` itself is excluded.
+                #[inline]
+                fn neg(self) -> Self {
+                    Self(-self.0)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! nonmax_neg {
+    ($($name:ident),*) => {
+        $(
+            impl $name {
+                /// Checked negation. Returns `None` if the value is `MIN`, which overflows, or `MIN + 1`, whose negation is the
+                /// reserved value.
+                #[inline]
+                pub const fn checked_neg(self) -> Option<Self> {
+                    match self.0.checked_neg() {
+                        Some(value) => Self::new(value),
+                        None => None,
+                    }
+                }
+            }
+
+            impl Neg for $name {
+                type Output = Self;
+
+                #[inline]
+                #[track_caller]
+                fn neg(self) -> Self {
+                    match self.checked_neg() {
+                        Some(value) => value,
+                        None => panic!(concat!(
+                            "attempt to compute `",
+                            stringify!($name),
+                            "` negation with overflow or a reserved result"
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+nonmax! {
+    NonMaxU8(u8, MAX, "`0..u8::MAX`");
+    NonMaxU16(u16, MAX, "`0..u16::MAX`");
+    NonMaxU32(u32, MAX, "`0..u32::MAX`");
+    NonMaxU64(u64, MAX, "`0..u64::MAX`");
+    NonMaxU128(u128, MAX, "`0..u128::MAX`");
+    NonMaxUsize(usize, MAX, "`0..usize::MAX`");
+    NonMaxI8(i8, MAX, "`i8::MIN..i8::MAX`");
+    NonMaxI16(i16, MAX, "`i16::MIN..i16::MAX`");
+    NonMaxI32(i32, MAX, "`i32::MIN..i32::MAX`");
+    NonMaxI64(i64, MAX, "`i64::MIN..i64::MAX`");
+    NonMaxI128(i128, MAX, "`i128::MIN..i128::MAX`");
+    NonMaxIsize(isize, MAX, "`isize::MIN..isize::MAX`");
+    NonMinI8(i8, MIN, "`i8::MIN + 1..=i8::MAX`");
+    NonMinI16(i16, MIN, "`i16::MIN + 1..=i16::MAX`");
+    NonMinI32(i32, MIN, "`i32::MIN + 1..=i32::MAX`");
+    NonMinI64(i64, MIN, "`i64::MIN + 1..=i64::MAX`");
+    NonMinI128(i128, MIN, "`i128::MIN + 1..=i128::MAX`");
+    NonMinIsize(isize, MIN, "`isize::MIN + 1..=isize::MAX`");
+}
+
+nonmax_neg!(
+    NonMaxI8,
+    NonMaxI16,
+    NonMaxI32,
+    NonMaxI64,
+    NonMaxI128,
+    NonMaxIsize
+);
+nonmin_neg!(
+    NonMinI8,
+    NonMinI16,
+    NonMinI32,
+    NonMinI64,
+    NonMinI128,
+    NonMinIsize
+);
+
+#[cfg(test)]
+mod tests {
+    use std::format;
+
+    use super::*;
+    use crate::IOption;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(NonMaxU32::new(u32::MAX), None);
+        assert_eq!(NonMaxU32::new(42).map(NonMaxU32::get), Some(42));
+        assert_eq!(NonMinI8::new(i8::MIN), None);
+        assert_eq!(NonMinI8::new(i8::MAX).map(NonMinI8::get), Some(i8::MAX));
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(NonMaxU16::MIN.get(), 0);
+        assert_eq!(NonMaxU16::MAX.get(), u16::MAX - 1);
+        assert_eq!(NonMinI64::MIN.get(), i64::MIN + 1);
+        assert_eq!(NonMinI64::MAX.get(), i64::MAX);
+    }
+
+    #[test]
+    fn test_nullable() {
+        assert!(NonMaxU8::NULL.is_null());
+        assert!(!NonMaxU8::MAX.is_null());
+        assert!(NonMinI32::NULL.is_null());
+        assert!(!NonMinI32::MIN.is_null());
+
+        let ioption = IOption::new(NonMaxU64::MAX);
+        assert!(ioption.is_some());
+        assert_eq!(
+            core::mem::size_of::<IOption<NonMaxU64>>(),
+            core::mem::size_of::<u64>()
+        );
+    }
+
+    #[test]
+    fn test_try_from() {
+        assert_eq!(NonMaxI16::try_from(i16::MAX), Err(ReservedValueError));
+        assert_eq!(NonMaxI16::try_from(-3).map(i16::from), Ok(-3));
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("12".parse::<NonMaxU8>(), Ok(NonMaxU8::new(12).unwrap()));
+        assert_eq!(
+            "255".parse::<NonMaxU8>(),
+            Err(ParseError::Reserved(ReservedValueError))
+        );
+        assert!(matches!("256".parse::<NonMaxU8>(), Err(ParseError::Int(_))));
+        assert!(matches!("x".parse::<NonMinI8>(), Err(ParseError::Int(_))));
+    }
+
+    #[test]
+    fn test_fmt() {
+        let value = NonMinI32::new(-5).unwrap();
+        assert_eq!(format!("{value}"), "-5");
+        assert_eq!(format!("{value:?}"), "-5");
+    }
+
+    #[test]
+    fn test_ord() {
+        let small = NonMaxI32::new(-1).unwrap();
+        let large = NonMaxI32::new(1).unwrap();
+        assert!(small < large);
+        assert_eq!(small.max(large), large);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let two = NonMaxU8::new(2).unwrap();
+        let three = NonMaxU8::new(3).unwrap();
+        assert_eq!((two + three).get(), 5);
+        assert_eq!((three - two).get(), 1);
+        assert_eq!((two * three).get(), 6);
+        assert_eq!((three / two).get(), 1);
+        assert_eq!((three % two).get(), 1);
+
+        let mut value = two;
+        value += three;
+        value *= two;
+        assert_eq!(value.get(), 10);
+
+        assert_eq!(
+            NonMaxU8::MAX.checked_add(NonMaxU8::MIN),
+            Some(NonMaxU8::MAX)
+        );
+        assert_eq!(
+            NonMaxU8::new(254)
+                .unwrap()
+                .checked_add(NonMaxU8::new(1).unwrap()),
+            None
+        );
+        assert_eq!(two.checked_div(NonMaxU8::MIN), None);
+
+        let value = NonMinI8::new(-7).unwrap();
+        assert_eq!((-value).get(), 7);
+        assert_eq!((-NonMinI8::MIN).get(), i8::MAX);
+    }
+
+    #[test]
+    fn test_nonmax_neg() {
+        let value = NonMaxI8::new(7).unwrap();
+        assert_eq!((-value).get(), -7);
+        assert_eq!((-NonMaxI8::MAX).get(), 1 - i8::MAX);
+        assert_eq!(NonMaxI8::MIN.checked_neg(), None);
+        assert_eq!(NonMaxI8::new(i8::MIN + 1).unwrap().checked_neg(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_nonmax_neg_overflow_panic() {
+        let _ = -NonMaxI8::MIN;
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_arithmetic_reserved_panic() {
+        let _ = NonMaxU8::new(254).unwrap() + NonMaxU8::new(1).unwrap();
+    }
+
+    mod conformance_non_max {
+        use super::NonMaxU32;
+
+        crate::nullable_conformance_tests!(NonMaxU32, samples = [NonMaxU32::MIN, NonMaxU32::MAX]);
+    }
+
+    mod conformance_non_min {
+        use super::NonMinI16;
+
+        crate::nullable_conformance_tests!(NonMinI16, samples = [NonMinI16::MIN, NonMinI16::MAX]);
+    }
+}
@@ -0,0 +1,284 @@
+//! Null policies, which choose the null value of the type stored in an [`IOption`].
+//!
+//! [`Nullable`] allows exactly one null value per type. A [`NullPolicy`] lifts that restriction by moving the choice into the second type parameter of [`IOption`],
+//! so the same type can use different null values in different places:
+//!
+//! ```rust
+//! use inline_option::{IOption, policy::{Max, Zero}};
+//!
+//! // Indices use `u32::MAX` as null...
+//! let index = IOption::<u32, Max>::new_with_policy(0);
+//! assert!(index.is_some());
+//!
+//! // ...while wire ids use `0`.
+//! let id = IOption::<u32, Zero>::new_with_policy(0);
+//! assert!(id.is_none());
+//! ```
+//!
+//! The default policy, [`DefaultPolicy`], defers to the [`Nullable`] impl of the type, so `IOption<T>` behaves exactly as it always has.
+//!
+//! Custom policies are zero-sized marker types implementing [`NullPolicy`]:
+//!
+//! ```rust
+//! use inline_option::{IOption, NullPolicy};
+//!
+//! struct Negative;
+//!
+//! impl NullPolicy<i32> for Negative {
+//!     const NULL: i32 = -1;
+//!
+//!     fn is_null(value: &i32) -> bool {
+//!         *value < 0
+//!     }
+//! }
+//!
+//! let opt = IOption::<i32, Negative>::new_with_policy(-7);
+//! assert!(opt.is_none());
+//! ```
+//!
+//! [`IOption`]: crate::IOption
+
+use crate::{Nullable, nan};
+
+/// A policy describing the null value of `T` when stored in an [`IOption`][crate::IOption].
+///
+/// This is the type-level counterpart of [`Nullable`]: the same rules apply, but the policy is a separate type, so `T` can have any number of them.
+pub trait NullPolicy<T> {
+    /// The null value for `T` under this policy.
+    const NULL: T;
+
+    /// Returns `true` if `value` is null under this policy. Typically, this is a comparison with the null value.
+    fn is_null(value: &T) -> bool;
+}
+
+/// The default policy, which uses the [`Nullable`] impl of the type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DefaultPolicy;
+
+impl<T: Nullable> NullPolicy<T> for DefaultPolicy {
+    const NULL: T = T::NULL;
+
+    #[inline]
+    fn is_null(value: &T) -> bool {
+        value.is_null()
+    }
+}
+
+/// Uses the maximum value of an integer or float type as null.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Max;
+
+/// Uses the minimum value of an integer or float type as null.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Min;
+
+/// Uses zero as null for integer types.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Zero;
+
+/// Uses the reserved quiet NaN bit pattern as null for float types.
+///
+/// See the [`nan`] module for the exact rule.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nan;
+
+macro_rules! impl_bound_policies {
+    ($($ty:ty),*) => {
+        $(
+            impl NullPolicy<$ty> for Max {
+                const NULL: $ty = <$ty>::MAX;
+
+                #[inline]
+                fn is_null(value: &$ty) -> bool {
+                    *value == <$ty>::MAX
+                }
+            }
+
+            impl NullPolicy<$ty> for Min {
+                const NULL: $ty = <$ty>::MIN;
+
+                #[inline]
+                fn is_null(value: &$ty) -> bool {
+                    *value == <$ty>::MIN
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_zero_policy {
+    ($($ty:ty),*) => {
+        $(
+            impl NullPolicy<$ty> for Zero {
+                const NULL: $ty = 0;
+
+                #[inline]
+                fn is_null(value: &$ty) -> bool {
+                    *value == 0
+                }
+            }
+        )*
+    };
+}
+
+impl_bound_policies!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+impl_zero_policy!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+macro_rules! impl_pair_policy {
+    ($($policy:ty),*) => {
+        $(
+            /// A pair is null if either of its elements is null.
+            impl<A, B> NullPolicy<(A, B)> for $policy
+            where
+                $policy: NullPolicy<A> + NullPolicy<B>,
+            {
+                const NULL: (A, B) = (
+                    <$policy as NullPolicy<A>>::NULL,
+                    <$policy as NullPolicy<B>>::NULL,
+                );
+
+                #[inline]
+                fn is_null(value: &(A, B)) -> bool {
+                    <$policy as NullPolicy<A>>::is_null(&value.0)
+                        || <$policy as NullPolicy<B>>::is_null(&value.1)
+                }
+            }
+        )*
+    };
+}
+
+impl_pair_policy!(Max, Min, Zero, Nan);
+
+impl NullPolicy<f32> for Nan {
+    const NULL: f32 = nan::F32_NULL;
+
+    #[inline]
+    fn is_null(value: &f32) -> bool {
+        nan::is_null_f32(*value)
+    }
+}
+
+impl NullPolicy<f64> for Nan {
+    const NULL: f64 = nan::F64_NULL;
+
+    #[inline]
+    fn is_null(value: &f64) -> bool {
+        nan::is_null_f64(*value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IOption;
+
+    #[test]
+    fn test_max() {
+        assert!(IOption::<u32, Max>::new_with_policy(u32::MAX).is_none());
+        assert!(IOption::<u32, Max>::new_with_policy(0).is_some());
+        assert!(IOption::<f32, Max>::new_with_policy(f32::MAX).is_none());
+    }
+
+    #[test]
+    fn test_min() {
+        assert!(IOption::<i8, Min>::new_with_policy(i8::MIN).is_none());
+        assert!(IOption::<i8, Min>::new_with_policy(i8::MAX).is_some());
+        assert!(IOption::<f64, Min>::new_with_policy(f64::MIN).is_none());
+    }
+
+    #[test]
+    fn test_zero() {
+        assert!(IOption::<u64, Zero>::none_with_policy().is_none());
+        assert!(IOption::<u64, Zero>::new_with_policy(0).is_none());
+        assert!(IOption::<u64, Zero>::new_with_policy(u64::MAX).is_some());
+    }
+
+    #[test]
+    fn test_nan() {
+        assert!(IOption::<f32, Nan>::none_with_policy().is_none());
+        assert!(IOption::<f32, Nan>::new_with_policy(f32::NAN).is_some());
+        assert!(IOption::<f64, Nan>::new_with_policy(f64::MAX).is_some());
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(
+            core::mem::size_of::<IOption<u16, Zero>>(),
+            core::mem::size_of::<u16>()
+        );
+        assert_eq!(
+            core::mem::size_of::<IOption<f64, Nan>>(),
+            core::mem::size_of::<f64>()
+        );
+    }
+
+    #[test]
+    fn test_combinators() {
+        let id = IOption::<u32, Zero>::new_with_policy(21);
+        assert_eq!(id.map(|value| value * 2).unwrap(), 42);
+        assert!(id.map(|_| 0u64).is_none());
+        assert!(id.filter(|value| *value > 100).is_none());
+        assert_eq!(
+            id.and_then(|value| IOption::new_with_policy(u64::from(value)))
+                .unwrap_or(0),
+            21
+        );
+        assert_eq!(id.or(IOption::new_with_policy(1)).unwrap(), 21);
+        assert_eq!(IOption::<u32, Zero>::default().unwrap_or(7), 7);
+
+        let mut id = id;
+        assert_eq!(id.take().unwrap(), 21);
+        assert!(id.is_none());
+        assert!(id.replace(5).is_none());
+        assert_eq!(*id.get_or_insert(9), 5);
+        assert_eq!(Option::from(id), Some(5));
+        assert_eq!(IOption::<u32, Zero>::from(None).into_inner(), 0);
+    }
+
+    #[test]
+    fn test_zip() {
+        let a = IOption::<u8, Zero>::new_with_policy(1);
+        let b = IOption::<u16, Zero>::new_with_policy(2);
+        assert_eq!(a.zip(b).unwrap(), (1, 2));
+        assert!(a.zip(IOption::<u16, _>::none_with_policy()).is_none());
+        assert_eq!(a.zip(b).unzip().1.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_auto_traits() {
+        struct Local(
+            core::marker::PhantomData<*const ()>,
+            core::marker::PhantomPinned,
+        );
+
+        impl NullPolicy<u32> for Local {
+            const NULL: u32 = 0;
+
+            fn is_null(value: &u32) -> bool {
+                *value == 0
+            }
+        }
+
+        fn assert_auto_traits<T: Send + Sync + Unpin>() {}
+        assert_auto_traits::<IOption<u32, Local>>();
+    }
+
+    #[test]
+    fn test_with_policy() {
+        let index = IOption::<u32, Max>::new_with_policy(0);
+        let id: IOption<u32, Zero> = index.with_policy();
+        assert!(id.is_none());
+
+        let index = IOption::<u32, Max>::none_with_policy();
+        let id: IOption<u32, Zero> = index.with_policy();
+        assert!(id.is_none());
+
+        let index = IOption::<u32, Max>::new_with_policy(3);
+        let id: IOption<u32, Zero> = index.with_policy();
+        assert_eq!(id.unwrap(), 3);
+    }
+}
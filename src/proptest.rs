@@ -0,0 +1,250 @@
+//! [`proptest`] strategies for [`IOption`] and [`Nullable`] values.
+//!
+//! None of these strategies ever produce a `Some` holding the null value, and the [`Arbitrary`] impl of `IOption`
+//! takes the probability of generating `None` as its parameter:
+//!
+//! ```rust
+//! use inline_option::{IOption, policy::Max};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn ids_are_never_some_max(id in any::<IOption<u32, Max>>()) {
+//!         prop_assert_ne!(id.as_ref(), Some(&u32::MAX));
+//!     }
+//!
+//!     fn mostly_none(id in inline_option::proptest::with_none_probability::<u32, Max, _>(0.9, 0..10u32)) {
+//!         prop_assert!(id.map_or(true, |id| id < 10));
+//!     }
+//! }
+//! # ids_are_never_some_max();
+//! # mostly_none();
+//! ```
+
+use core::fmt;
+
+use ::proptest::{
+    arbitrary::{Arbitrary, StrategyFor, any, any_with},
+    option::{self, OptionStrategy, Probability},
+    strategy::{Filter, Map, NewTree, Strategy, ValueTree},
+    test_runner::TestRunner,
+};
+
+use crate::{DefaultPolicy, IOption, NullPolicy, Nullable};
+
+/// A strategy generating any value of `T` that is not null.
+pub fn non_null<T: Arbitrary + Nullable>() -> impl Strategy<Value = T> {
+    non_null_with_policy::<T, DefaultPolicy>()
+}
+
+/// A strategy generating any value of `T` that is not null under the policy `P`.
+pub fn non_null_with_policy<T: Arbitrary, P: NullPolicy<T>>() -> impl Strategy<Value = T> {
+    any::<T>().prop_filter("null value", |value| !P::is_null(value))
+}
+
+/// A strategy generating `None` half of the time, and a non-null value from `values` otherwise.
+pub fn of<T, P, S>(values: S) -> impl Strategy<Value = IOption<T, P>>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    with_none_probability(0.5, values)
+}
+
+/// A strategy generating `None` with the given probability, and a non-null value from `values` otherwise.
+///
+/// Null values generated by `values` are rejected, so `values` should rarely generate them.
+///
+/// # Panics
+///
+/// Panics if the probability is outside of `[0.0, 1.0]`, like [`Probability::new`].
+pub fn with_none_probability<T, P, S>(
+    probability: impl Into<Probability>,
+    values: S,
+) -> impl Strategy<Value = IOption<T, P>>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    IOptionStrategy::new(probability.into(), values)
+}
+
+type SomeOrNone<S, T, P> =
+    Map<OptionStrategy<Filter<S, fn(&T) -> bool>>, fn(Option<T>) -> IOption<T, P>>;
+type AlwaysSome<S, T, P> = Map<Filter<S, fn(&T) -> bool>, fn(T) -> IOption<T, P>>;
+type ChoiceTree<S, T, P> =
+    Choice<<SomeOrNone<S, T, P> as Strategy>::Tree, <AlwaysSome<S, T, P> as Strategy>::Tree>;
+
+/// Picks between `None` and `Some`, or always generates one of them when the probability is 0.0 or 1.0.
+#[derive(Debug, Clone)]
+enum Choice<W, S> {
+    Weighted(W),
+    AlwaysSome(S),
+    AlwaysNone,
+}
+
+/// The strategy returned by [`with_none_probability`] and used by the [`Arbitrary`] impl of `IOption`.
+#[must_use = "strategies do nothing unless used"]
+pub struct IOptionStrategy<S, T, P>(Choice<SomeOrNone<S, T, P>, AlwaysSome<S, T, P>>)
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>;
+
+impl<S, T, P> IOptionStrategy<S, T, P>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    fn new(none_probability: Probability, values: S) -> Self {
+        let none_probability = f64::from(none_probability);
+        let is_some: fn(&T) -> bool = |value| !P::is_null(value);
+        let values = values.prop_filter("null value", is_some);
+        Self(if none_probability == 0.0 {
+            Choice::AlwaysSome(values.prop_map(IOption::new_with_policy as fn(T) -> IOption<T, P>))
+        } else if none_probability == 1.0 {
+            Choice::AlwaysNone
+        } else {
+            Choice::Weighted(
+                option::weighted(1.0 - none_probability, values)
+                    .prop_map(IOption::from as fn(Option<T>) -> IOption<T, P>),
+            )
+        })
+    }
+}
+
+impl<S, T, P> fmt::Debug for IOptionStrategy<S, T, P>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("IOptionStrategy").field(&self.0).finish()
+    }
+}
+
+impl<S, T, P> Strategy for IOptionStrategy<S, T, P>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    type Tree = IOptionValueTree<S, T, P>;
+    type Value = IOption<T, P>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        Ok(IOptionValueTree(match &self.0 {
+            Choice::Weighted(strategy) => Choice::Weighted(strategy.new_tree(runner)?),
+            Choice::AlwaysSome(strategy) => Choice::AlwaysSome(strategy.new_tree(runner)?),
+            Choice::AlwaysNone => Choice::AlwaysNone,
+        }))
+    }
+}
+
+/// The value tree of [`IOptionStrategy`].
+pub struct IOptionValueTree<S, T, P>(ChoiceTree<S, T, P>)
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>;
+
+impl<S, T, P> ValueTree for IOptionValueTree<S, T, P>
+where
+    T: fmt::Debug,
+    P: NullPolicy<T>,
+    S: Strategy<Value = T>,
+{
+    type Value = IOption<T, P>;
+
+    fn current(&self) -> IOption<T, P> {
+        match &self.0 {
+            Choice::Weighted(tree) => tree.current(),
+            Choice::AlwaysSome(tree) => tree.current(),
+            Choice::AlwaysNone => IOption::none_with_policy(),
+        }
+    }
+
+    fn simplify(&mut self) -> bool {
+        match &mut self.0 {
+            Choice::Weighted(tree) => tree.simplify(),
+            Choice::AlwaysSome(tree) => tree.simplify(),
+            Choice::AlwaysNone => false,
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match &mut self.0 {
+            Choice::Weighted(tree) => tree.complicate(),
+            Choice::AlwaysSome(tree) => tree.complicate(),
+            Choice::AlwaysNone => false,
+        }
+    }
+}
+
+/// Generates `None` with the probability given as the first parameter (one half by default), and any non-null value otherwise.
+impl<T: Arbitrary, P: NullPolicy<T>> Arbitrary for IOption<T, P> {
+    type Parameters = (Probability, T::Parameters);
+    type Strategy = IOptionStrategy<StrategyFor<T>, T, P>;
+
+    fn arbitrary_with((none_probability, args): Self::Parameters) -> Self::Strategy {
+        IOptionStrategy::new(none_probability, any_with::<T>(args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::prelude::*;
+
+    use super::*;
+    use crate::policy::{Max, Zero};
+
+    proptest! {
+        #[test]
+        fn test_arbitrary(id in any::<IOption<u8, Zero>>()) {
+            prop_assert_ne!(id.as_ref(), Some(&0));
+        }
+
+        #[test]
+        fn test_non_null(id in non_null_with_policy::<u8, Max>()) {
+            prop_assert_ne!(id, u8::MAX);
+        }
+
+        #[test]
+        fn test_of(id in of::<u8, Zero, _>(0..2u8)) {
+            prop_assert_ne!(id.as_ref(), Some(&0));
+        }
+    }
+
+    #[test]
+    fn test_none_probability_bounds() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = with_none_probability::<u32, Max, _>(1.0, any::<u32>());
+        assert!((0..64).all(|_| strategy.new_tree(&mut runner).unwrap().current().is_none()));
+
+        let strategy = any_with::<IOption<u8, Zero>>((0.0.into(), ()));
+        for _ in 0..64 {
+            let mut tree = strategy.new_tree(&mut runner).unwrap();
+            while tree.simplify() {}
+            assert!(tree.current().is_some());
+        }
+    }
+
+    #[test]
+    fn test_none_probability() {
+        let mut runner = TestRunner::deterministic();
+        let strategy = with_none_probability::<u32, Max, _>(0.9, any::<u32>());
+        let nones = (0..256)
+            .filter(|_| strategy.new_tree(&mut runner).unwrap().current().is_none())
+            .count();
+        assert!(nones > 192);
+
+        let strategy = any_with::<IOption<u32, Max>>((0.1.into(), ()));
+        let nones = (0..256)
+            .filter(|_| strategy.new_tree(&mut runner).unwrap().current().is_none())
+            .count();
+        assert!(nones < 64);
+    }
+}
@@ -0,0 +1,337 @@
+//! rkyv support for [`IOption`].
+//!
+//! An `IOption<T, P>` archives as `IOption<T::Archived, P>`, so an archived `IOption` is exactly as large as the archived value
+//! and can be checked for `None` in place, without deserializing. This requires the policy to also describe the archived type;
+//! for [`DefaultPolicy`](crate::DefaultPolicy), that means `T::Archived: Nullable`.
+//!
+//! The archived integer and float types from [`rkyv::primitive`](::rkyv::primitive) implement [`Nullable`] with the same
+//! null value as their native types (behind the `nullable-core-ints` and `nullable-core-floats` features), and support the
+//! same [policies](crate::policy) as their native types.
+//!
+//! Serializing an `IOption` panics if the archived value is null and the `IOption` is `Some`, or the archived value is not null
+//! and the `IOption` is `None`, instead of silently changing it. This can happen when rkyv archives `isize` and `usize` as
+//! narrower integers: `isize`'s null value under [`DefaultPolicy`](crate::DefaultPolicy), [`Max`] and [`Min`] does not survive,
+//! and neither does a `usize` that truncates to the archived null value.
+
+use core::marker::PhantomData;
+
+use ::rkyv::{
+    Archive, Deserialize, Place, Portable, Serialize,
+    bytecheck::CheckBytes,
+    primitive::{
+        ArchivedF32, ArchivedF64, ArchivedI16, ArchivedI32, ArchivedI64, ArchivedI128, ArchivedU16,
+        ArchivedU32, ArchivedU64, ArchivedU128,
+    },
+    rancor::Fallible,
+};
+
+use crate::{
+    IOption, NullPolicy, nan,
+    policy::{Max, Min, Nan, Zero},
+};
+
+// SAFETY: `IOption` is `#[repr(transparent)]` over `T`, so it has the same stable layout as `T`.
+unsafe impl<T: Portable, P: NullPolicy<T>> Portable for IOption<T, P> {}
+
+// SAFETY: `IOption` is `#[repr(transparent)]` over `T`, and every valid `T` is a valid `IOption`.
+unsafe impl<T: CheckBytes<C>, P: NullPolicy<T>, C: Fallible + ?Sized> CheckBytes<C>
+    for IOption<T, P>
+{
+    #[inline]
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        // SAFETY: The caller guarantees that `value` is aligned and points to enough bytes for `Self`, which is laid out as `T`.
+        unsafe { T::check_bytes(value.cast::<T>(), context) }
+    }
+}
+
+impl<T: Archive, P: NullPolicy<T> + NullPolicy<T::Archived>> Archive for IOption<T, P> {
+    type Archived = IOption<T::Archived, P>;
+    type Resolver = T::Resolver;
+
+    /// # Panics
+    ///
+    /// Panics if the archived value is null and `self` is `Some`, or the archived value is not null and `self` is `None`.
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `IOption<T::Archived, P>` is `#[repr(transparent)]` over `T::Archived`.
+        let out = unsafe { out.cast_unchecked::<T::Archived>() };
+        self.0.resolve(resolver, out);
+        // SAFETY: `resolve` initialized `out` with a valid `T::Archived`.
+        let archived = unsafe { &*out.ptr() };
+        assert!(
+            <P as NullPolicy<T::Archived>>::is_null(archived) == self.is_none(),
+            "archived value of `IOption` does not match its null value",
+        );
+    }
+}
+
+impl<T, P, S> Serialize<S> for IOption<T, P>
+where
+    T: Serialize<S>,
+    P: NullPolicy<T> + NullPolicy<T::Archived>,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<T, P, D> Deserialize<IOption<T, P>, D> for IOption<T::Archived, P>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    P: NullPolicy<T> + NullPolicy<T::Archived>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<IOption<T, P>, D::Error> {
+        if self.is_none() {
+            return Ok(IOption::none_with_policy());
+        }
+        Ok(IOption(self.0.deserialize(deserializer)?, PhantomData))
+    }
+}
+
+macro_rules! impl_archived_int {
+    ($($archived:ty => $ty:ty),*) => {
+        $(
+            #[cfg(feature = "nullable-core-ints")]
+            impl crate::Nullable for $archived {
+                const NULL: Self = <$archived>::from_native(<$ty>::MAX);
+
+                #[inline]
+                fn is_null(&self) -> bool {
+                    self.to_native() == <$ty>::MAX
+                }
+            }
+
+            impl NullPolicy<$archived> for Max {
+                const NULL: $archived = <$archived>::from_native(<$ty>::MAX);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    value.to_native() == <$ty>::MAX
+                }
+            }
+
+            impl NullPolicy<$archived> for Min {
+                const NULL: $archived = <$archived>::from_native(<$ty>::MIN);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    value.to_native() == <$ty>::MIN
+                }
+            }
+
+            impl NullPolicy<$archived> for Zero {
+                const NULL: $archived = <$archived>::from_native(0);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    value.to_native() == 0
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_archived_float {
+    ($($archived:ty => $ty:ty, $null:expr, $is_null:path),*) => {
+        $(
+            #[cfg(feature = "nullable-core-floats")]
+            impl crate::Nullable for $archived {
+                const NULL: Self = <$archived>::from_native($null);
+
+                #[inline]
+                fn is_null(&self) -> bool {
+                    $is_null(self.to_native())
+                }
+            }
+
+            impl NullPolicy<$archived> for Max {
+                const NULL: $archived = <$archived>::from_native(<$ty>::MAX);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    value.to_native() == <$ty>::MAX
+                }
+            }
+
+            impl NullPolicy<$archived> for Min {
+                const NULL: $archived = <$archived>::from_native(<$ty>::MIN);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    value.to_native() == <$ty>::MIN
+                }
+            }
+
+            impl NullPolicy<$archived> for Nan {
+                const NULL: $archived = <$archived>::from_native($null);
+
+                #[inline]
+                fn is_null(value: &$archived) -> bool {
+                    $is_null(value.to_native())
+                }
+            }
+        )*
+    };
+}
+
+impl_archived_int!(
+    ArchivedI16 => i16,
+    ArchivedI32 => i32,
+    ArchivedI64 => i64,
+    ArchivedI128 => i128,
+    ArchivedU16 => u16,
+    ArchivedU32 => u32,
+    ArchivedU64 => u64,
+    ArchivedU128 => u128
+);
+impl_archived_float!(
+    ArchivedF32 => f32, nan::F32_NULL, nan::is_null_f32,
+    ArchivedF64 => f64, nan::F64_NULL, nan::is_null_f64
+);
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use std::vec::Vec;
+
+    use ::rkyv::{rancor::Error, util::AlignedVec};
+
+    use crate::{
+        IOption, Nullable,
+        policy::{Max, Min, Zero},
+    };
+
+    type Id = IOption<u32, Max>;
+
+    #[derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize, Debug, PartialEq)]
+    struct Handle {
+        index: u32,
+        generation: u32,
+    }
+
+    impl Nullable for Handle {
+        const NULL: Self = Handle {
+            index: u32::MAX,
+            generation: 0,
+        };
+
+        fn is_null(&self) -> bool {
+            self.index == u32::MAX
+        }
+    }
+
+    impl Nullable for ArchivedHandle {
+        const NULL: Self = ArchivedHandle {
+            index: ::rkyv::rend::u32_le::from_native(u32::MAX),
+            generation: ::rkyv::rend::u32_le::from_native(0),
+        };
+
+        fn is_null(&self) -> bool {
+            self.index == u32::MAX
+        }
+    }
+
+    fn to_bytes(ids: &[Id]) -> AlignedVec {
+        ::rkyv::to_bytes::<Error>(&ids.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let ids = [
+            Id::new_with_policy(1),
+            Id::none_with_policy(),
+            Id::new_with_policy(3),
+        ];
+        let bytes = to_bytes(&ids);
+        let archived = ::rkyv::access::<::rkyv::Archived<Vec<Id>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert!(archived[0].is_some());
+        assert!(archived[1].is_none());
+        assert_eq!(archived[2].unwrap().to_native(), 3);
+
+        let deserialized = ::rkyv::deserialize::<Vec<Id>, Error>(archived).unwrap();
+        assert_eq!(deserialized, ids);
+    }
+
+    #[test]
+    fn test_roundtrip_derived() {
+        let handles = [
+            IOption::new(Handle {
+                index: 4,
+                generation: 1,
+            }),
+            IOption::none(),
+        ];
+        let bytes = ::rkyv::to_bytes::<Error>(&handles).unwrap();
+        let archived =
+            ::rkyv::access::<::rkyv::Archived<[IOption<Handle>; 2]>, Error>(&bytes).unwrap();
+        assert_eq!(archived[0].as_ref().unwrap().generation, 1);
+        assert!(archived[1].is_none());
+
+        let deserialized = ::rkyv::deserialize::<[IOption<Handle>; 2], Error>(archived).unwrap();
+        assert_eq!(deserialized, handles);
+    }
+
+    #[test]
+    fn test_roundtrip_isize_usize() {
+        let values = (
+            IOption::<isize, Zero>::none_with_policy(),
+            IOption::<usize, Max>::none_with_policy(),
+            IOption::<usize, Max>::new_with_policy(7),
+        );
+        let bytes = ::rkyv::to_bytes::<Error>(&values).unwrap();
+        let deserialized = ::rkyv::from_bytes::<
+            (
+                IOption<isize, Zero>,
+                IOption<usize, Max>,
+                IOption<usize, Max>,
+            ),
+            Error,
+        >(&bytes)
+        .unwrap();
+        assert_eq!(deserialized, values);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic = "archived value of `IOption` does not match its null value"]
+    fn test_archived_null_mismatch_none() {
+        let value = IOption::<isize, Min>::none_with_policy();
+        let _ = ::rkyv::to_bytes::<Error>(&value);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic = "archived value of `IOption` does not match its null value"]
+    fn test_archived_null_mismatch_some() {
+        let value = IOption::<usize, Max>::new_with_policy(0xFFFF_FFFF);
+        let _ = ::rkyv::to_bytes::<Error>(&value);
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(
+            core::mem::size_of::<::rkyv::Archived<Id>>(),
+            core::mem::size_of::<u32>()
+        );
+        assert!(
+            core::mem::size_of::<::rkyv::Archived<Option<u32>>>() > core::mem::size_of::<u32>()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "nullable-core-ints", feature = "nullable-core-floats"))]
+    fn test_default_policy() {
+        let value = (IOption::new(7u64), IOption::<f64>::none());
+        let bytes = ::rkyv::to_bytes::<Error>(&value).unwrap();
+        let archived =
+            ::rkyv::access::<::rkyv::Archived<(IOption<u64>, IOption<f64>)>, Error>(&bytes)
+                .unwrap();
+        assert_eq!(archived.0.unwrap().to_native(), 7);
+        assert!(archived.1.is_none());
+    }
+}
@@ -0,0 +1,284 @@
+//! Serde support for [`IOption`] and bare [`Nullable`] values.
+//!
+//! In human-readable formats such as JSON, an `IOption<T>` is serialized exactly like an `Option<T>`: `None` becomes `null`.
+//! Deserializing rejects a `Some` whose value is the null value, since it could never be represented.
+//!
+//! In compact formats such as bincode, the inner value is serialized as-is, so `None` is written as the null value with no extra tag.
+//!
+//! Fields that store a bare [`Nullable`] value instead of an `IOption` can get the same `Option`-like representation with [`as_option`].
+//! Together with [`is_null`] and [`null`], the field can be left out entirely when it is null:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! # use inline_option::Nullable;
+//! # #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+//! # struct NodeId(u32);
+//! # impl Nullable for NodeId {
+//! #     const NULL: Self = NodeId(u32::MAX);
+//! #     fn is_null(&self) -> bool { self.0 == u32::MAX }
+//! # }
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Node {
+//!     #[serde(
+//!         with = "inline_option::serde::as_option",
+//!         skip_serializing_if = "inline_option::serde::is_null",
+//!         default = "inline_option::serde::null"
+//!     )]
+//!     parent: NodeId,
+//! }
+//!
+//! let root = Node { parent: NodeId::NULL };
+//! assert_eq!(serde_json::to_string(&root).unwrap(), "{}");
+//! assert_eq!(serde_json::from_str::<Node>("{}").unwrap(), root);
+//! assert_eq!(serde_json::from_str::<Node>(r#"{"parent":null}"#).unwrap(), root);
+//!
+//! let child = Node { parent: NodeId(0) };
+//! assert_eq!(serde_json::to_string(&child).unwrap(), r#"{"parent":0}"#);
+//! ```
+
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::{IOption, NullPolicy, Nullable};
+
+/// Returns `true` if `value` is null. Meant for `#[serde(skip_serializing_if = "inline_option::serde::is_null")]`.
+#[inline]
+pub fn is_null<T: Nullable>(value: &T) -> bool {
+    value.is_null()
+}
+
+/// Returns the null value. Meant for `#[serde(default = "inline_option::serde::null")]`, so a missing field is null.
+#[inline]
+pub fn null<T: Nullable>() -> T {
+    T::NULL
+}
+
+/// Serializes a bare [`Nullable`] value like an `Option`, with the null value as `None`.
+///
+/// Use it as `#[serde(with = "inline_option::serde::as_option")]`. See the [module-level documentation](self) for an example.
+pub mod as_option {
+    use ::serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+    use crate::{IOption, Nullable};
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Nullable + Serialize,
+        S: Serializer,
+    {
+        if value.is_null() {
+            serializer.serialize_none()
+        } else {
+            serializer.serialize_some(value)
+        }
+    }
+
+    /// Deserializes `None` as the null value, and rejects a `Some` whose value is the null value.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: Nullable + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        match Option::<T>::deserialize(deserializer)? {
+            Some(value) => IOption::try_some(value)
+                .map(IOption::into_inner)
+                .map_err(de::Error::custom),
+            None => Ok(T::NULL),
+        }
+    }
+}
+
+impl<T: Serialize, P: NullPolicy<T>> Serialize for IOption<T, P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            match self.as_ref() {
+                Some(value) => serializer.serialize_some(value),
+                None => serializer.serialize_none(),
+            }
+        } else {
+            serializer.serialize_newtype_struct("IOption", &self.0)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, P: NullPolicy<T>> Deserialize<'de> for IOption<T, P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            match Option::<T>::deserialize(deserializer)? {
+                Some(value) => IOption::try_some_with_policy(value).map_err(de::Error::custom),
+                None => Ok(IOption::none_with_policy()),
+            }
+        } else {
+            deserializer.deserialize_newtype_struct("IOption", RawVisitor(PhantomData))
+        }
+    }
+}
+
+/// Deserializes the raw inner value, where the null value means `None`.
+struct RawVisitor<T, P>(PhantomData<(T, P)>);
+
+impl<'de, T: Deserialize<'de>, P: NullPolicy<T>> de::Visitor<'de> for RawVisitor<T, P> {
+    type Value = IOption<T, P>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("tuple struct IOption")
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        T::deserialize(deserializer).map(IOption::new_with_policy)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        match seq.next_element()? {
+            Some(value) => Ok(IOption::new_with_policy(value)),
+            None => Err(de::Error::invalid_length(0, &self)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::serde::{Deserialize, Serialize};
+    use serde_test::{Configure, Token, assert_de_tokens_error, assert_tokens};
+
+    use crate::{IOption, Nullable, policy::Max};
+
+    type Id = IOption<u32, Max>;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    struct NodeId(u32);
+
+    impl Nullable for NodeId {
+        const NULL: Self = NodeId(u32::MAX);
+
+        fn is_null(&self) -> bool {
+            self.0 == u32::MAX
+        }
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Node {
+        #[serde(with = "super::as_option")]
+        parent: NodeId,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SparseNode {
+        #[serde(
+            with = "super::as_option",
+            skip_serializing_if = "super::is_null",
+            default = "super::null"
+        )]
+        parent: NodeId,
+    }
+
+    #[test]
+    fn test_readable_some() {
+        let ioption = Id::new_with_policy(42);
+        assert_tokens(&ioption.readable(), &[Token::Some, Token::U32(42)]);
+    }
+
+    #[test]
+    fn test_readable_none() {
+        let ioption = Id::none_with_policy();
+        assert_tokens(&ioption.readable(), &[Token::None]);
+    }
+
+    #[test]
+    fn test_readable_sentinel_rejected() {
+        assert_de_tokens_error::<serde_test::Readable<Id>>(
+            &[Token::Some, Token::U32(u32::MAX)],
+            "value collides with the null value of `IOption`",
+        );
+    }
+
+    #[test]
+    fn test_compact_some() {
+        let ioption = Id::new_with_policy(42);
+        assert_tokens(
+            &ioption.compact(),
+            &[Token::NewtypeStruct { name: "IOption" }, Token::U32(42)],
+        );
+    }
+
+    #[test]
+    fn test_as_option() {
+        let node = Node { parent: NodeId(3) };
+        let tokens = [
+            Token::Struct {
+                name: "Node",
+                len: 1,
+            },
+            Token::Str("parent"),
+            Token::Some,
+            Token::NewtypeStruct { name: "NodeId" },
+            Token::U32(3),
+            Token::StructEnd,
+        ];
+        assert_tokens(&node, &tokens);
+
+        let node = Node {
+            parent: NodeId::NULL,
+        };
+        let tokens = [
+            Token::Struct {
+                name: "Node",
+                len: 1,
+            },
+            Token::Str("parent"),
+            Token::None,
+            Token::StructEnd,
+        ];
+        assert_tokens(&node, &tokens);
+    }
+
+    #[test]
+    fn test_as_option_sentinel_rejected() {
+        assert_de_tokens_error::<Node>(
+            &[
+                Token::Struct {
+                    name: "Node",
+                    len: 1,
+                },
+                Token::Str("parent"),
+                Token::Some,
+                Token::NewtypeStruct { name: "NodeId" },
+                Token::U32(u32::MAX),
+                Token::StructEnd,
+            ],
+            "value collides with the null value of `IOption`",
+        );
+    }
+
+    #[test]
+    fn test_skip_null() {
+        let node = SparseNode {
+            parent: NodeId::NULL,
+        };
+        let tokens = [
+            Token::Struct {
+                name: "SparseNode",
+                len: 0,
+            },
+            Token::StructEnd,
+        ];
+        assert_tokens(&node, &tokens);
+    }
+
+    #[test]
+    fn test_compact_none() {
+        let ioption = Id::none_with_policy();
+        assert_tokens(
+            &ioption.compact(),
+            &[
+                Token::NewtypeStruct { name: "IOption" },
+                Token::U32(u32::MAX),
+            ],
+        );
+    }
+}
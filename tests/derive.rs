@@ -0,0 +1,170 @@
+#![cfg(feature = "derive")]
+
+use inline_option::{IOption, Nullable};
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(null = Value(i32::MAX))]
+struct Value(i32);
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(null = Point { x: f32::MIN, y: f32::MIN })]
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Nullable, Clone, Copy, Debug)]
+enum Slot {
+    Occupied(u16),
+    #[nullable]
+    Empty,
+}
+
+#[derive(Nullable, Clone, Copy, Debug)]
+#[nullable(null = Reading { value: f64::NAN, sensor: 0 })]
+struct Reading {
+    value: f64,
+    sensor: u8,
+}
+
+#[derive(Nullable, Clone, Copy, Debug)]
+#[nullable(null = Sample::Value(f32::NAN))]
+enum Sample {
+    Value(f32),
+    Missing,
+}
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(null = Wrapper(T::NULL))]
+struct Wrapper<T: Nullable>(T);
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(null = Id(u32::MAX))]
+struct Id(u32);
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(via = id)]
+struct Handle {
+    id: Id,
+    generation: u16,
+}
+
+#[derive(Nullable, Clone, Copy, Debug, PartialEq)]
+#[nullable(via = 1)]
+struct Labeled(
+    #[nullable(null = b'?')] u8,
+    Id,
+    #[nullable(null = core::marker::PhantomData)] core::marker::PhantomData<f32>,
+);
+
+#[test]
+fn test_derive_tuple_struct() {
+    assert_eq!(Value::NULL, Value(i32::MAX));
+    assert!(Value(i32::MAX).is_null());
+    assert!(!Value(42).is_null());
+    assert!(IOption::new(Value(i32::MAX)).is_none());
+    assert_eq!(IOption::new(Value(42)).unwrap(), Value(42));
+}
+
+#[test]
+fn test_derive_named_struct() {
+    assert!(Point::NULL.is_null());
+    assert!(
+        !Point {
+            x: f32::MIN,
+            y: 0.0
+        }
+        .is_null()
+    );
+    assert_eq!(
+        core::mem::size_of::<IOption<Point>>(),
+        core::mem::size_of::<Point>()
+    );
+}
+
+#[test]
+fn test_derive_nan_null() {
+    assert!(Reading::NULL.is_null());
+    assert!(IOption::<Reading>::none().is_none());
+    assert!(
+        !Reading {
+            value: f64::NAN,
+            sensor: 1
+        }
+        .is_null()
+    );
+    assert!(
+        !Reading {
+            value: 0.0,
+            sensor: 0
+        }
+        .is_null()
+    );
+
+    assert!(Sample::NULL.is_null());
+    assert!(IOption::<Sample>::none().is_none());
+    assert!(!Sample::Value(1.0).is_null());
+    assert!(!Sample::Missing.is_null());
+}
+
+#[test]
+fn test_derive_generic() {
+    assert!(Wrapper::<Value>::NULL.is_null());
+    assert!(!Wrapper(Value(1)).is_null());
+}
+
+#[test]
+fn test_derive_enum_variant() {
+    assert!(matches!(Slot::NULL, Slot::Empty));
+    assert!(Slot::Empty.is_null());
+    assert!(!Slot::Occupied(0).is_null());
+
+    let mut ioption = IOption::<Slot>::none();
+    assert!(ioption.is_none());
+    ioption.replace(Slot::Occupied(7));
+    assert!(matches!(ioption.unwrap(), Slot::Occupied(7)));
+}
+
+#[test]
+fn test_derive_via_named() {
+    assert_eq!(
+        Handle::NULL,
+        Handle {
+            id: Id::NULL,
+            generation: 0
+        }
+    );
+    assert!(
+        Handle {
+            id: Id::NULL,
+            generation: 3
+        }
+        .is_null()
+    );
+    assert!(
+        !Handle {
+            id: Id(0),
+            generation: 0
+        }
+        .is_null()
+    );
+    assert_eq!(
+        core::mem::size_of::<IOption<Handle>>(),
+        core::mem::size_of::<Handle>()
+    );
+}
+
+#[test]
+fn test_derive_via_unnamed() {
+    assert_eq!(
+        Labeled::NULL,
+        Labeled(b'?', Id::NULL, core::marker::PhantomData)
+    );
+    assert!(Labeled(b'a', Id::NULL, core::marker::PhantomData).is_null());
+    assert!(!Labeled(b'?', Id(1), core::marker::PhantomData).is_null());
+}
+
+#[test]
+fn test_derive_errors() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,16 @@
+use inline_option::Nullable;
+
+type Maybe = Option<u8>;
+
+#[derive(Nullable)]
+#[nullable(null = Id(u32::MAX))]
+struct Id(u32);
+
+#[derive(Nullable)]
+#[nullable(via = id)]
+struct Handle {
+    id: Id,
+    tag: Maybe,
+}
+
+fn main() {}
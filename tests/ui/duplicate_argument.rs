@@ -0,0 +1,7 @@
+use inline_option::Nullable;
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Id(0), null = Id(1))]
+struct Id(u32);
+
+fn main() {}
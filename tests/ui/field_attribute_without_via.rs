@@ -0,0 +1,18 @@
+use inline_option::Nullable;
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Handle { id: 0, generation: 0 })]
+struct Handle {
+    #[nullable(null = 5, via = x)]
+    id: u32,
+    generation: u16,
+}
+
+#[derive(Nullable)]
+enum Slot {
+    Occupied(#[nullable(null = 0)] u32),
+    #[nullable]
+    Empty,
+}
+
+fn main() {}
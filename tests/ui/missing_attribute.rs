@@ -0,0 +1,12 @@
+use inline_option::Nullable;
+
+#[derive(Nullable)]
+struct Id(u32);
+
+#[derive(Nullable)]
+enum Slot {
+    Occupied(u32),
+    Empty,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use inline_option::Nullable;
+
+#[derive(Nullable)]
+enum Slot {
+    Occupied(u32),
+    #[nullable]
+    Empty(()),
+}
+
+fn main() {}
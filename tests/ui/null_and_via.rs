@@ -0,0 +1,14 @@
+use inline_option::Nullable;
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Id(0))]
+struct Id(u32);
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Handle { id: Id(0), generation: 0 }, via = id)]
+struct Handle {
+    id: Id,
+    generation: u16,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use inline_option::Nullable;
+
+#[derive(Nullable)]
+enum Slot {
+    Occupied(u32),
+    #[nullable]
+    Empty,
+    #[nullable]
+    Vacant,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use inline_option::Nullable;
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Id(0))]
+struct Id(u32);
+
+#[derive(Nullable)]
+#[nullable(via = index)]
+struct Handle {
+    id: Id,
+    generation: u16,
+}
+
+fn main() {}
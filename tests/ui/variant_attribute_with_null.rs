@@ -0,0 +1,11 @@
+use inline_option::Nullable;
+
+#[derive(Nullable, PartialEq)]
+#[nullable(null = Slot::Occupied(0))]
+enum Slot {
+    Occupied(u32),
+    #[nullable]
+    Empty,
+}
+
+fn main() {}
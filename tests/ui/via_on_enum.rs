@@ -0,0 +1,10 @@
+use inline_option::Nullable;
+
+#[derive(Nullable)]
+#[nullable(via = 0)]
+enum Slot {
+    Occupied(u32),
+    Empty,
+}
+
+fn main() {}